@@ -0,0 +1,649 @@
+use std::fmt;
+use thiserror::Error;
+
+// the gremlin-groovy subset of the TinkerPop grammar shared by gremlin_rs::process::parser and
+// the gremlin! macro: a source (g or another alias) or __, source configuration steps, traversal
+// steps with literal, list/map, enum, predicate, strategy and nested traversal arguments, and an
+// optional terminal step which is dropped. the syntax tree is lowered to bytecode at runtime and
+// to builder calls at compile time
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ParseError {
+    #[error("unexpected end of query: expected {0}")]
+    Eof(&'static str),
+    #[error("invalid syntax at {0}: expected {1} - got {2}")]
+    InvalidSyntax(usize, &'static str, String),
+    #[error("unknown {1} at {0}: {2}")]
+    Unknown(usize, &'static str, String),
+}
+
+use ParseError::*;
+
+#[derive(Debug, PartialEq)]
+pub struct Query {
+    // None for an anonymous __ traversal
+    pub source: Option<String>,
+    pub configuration: Vec<Step>,
+    pub steps: Vec<Step>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Step {
+    pub pos: usize,
+    pub op: String,
+    pub args: Vec<Value>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Number {
+    Integer(i32),
+    Long(i64),
+    Float(f32),
+    Double(f64),
+}
+
+impl fmt::Display for Number {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Number::Integer(i) => write!(f, "{}", i),
+            Number::Long(l) => write!(f, "{}", l),
+            Number::Float(x) => write!(f, "{}", x),
+            Number::Double(x) => write!(f, "{}", x),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Value {
+    Str(String),
+    // a number without l/i/f/d suffix is unsuffixed, integers outside the i32 range are Long
+    Number {
+        pos: usize,
+        value: Number,
+        suffixed: bool,
+    },
+    Bool(bool),
+    Null,
+    // $name, only when parsing with variables
+    Variable(String),
+    List(Vec<Value>),
+    // keys are Str for quoted or bare keys, any value for (key)
+    Map(Vec<(Value, Value)>),
+    // a class and name out of TOKENS
+    Token(&'static str, &'static str),
+    // P or TextP, the arguments are checked where the predicate is built
+    Predicate {
+        pos: usize,
+        class: &'static str,
+        op: String,
+        args: Vec<Value>,
+        modifiers: Vec<Step>,
+    },
+    // new Name(key: value, ...) or a bare Name ending in Strategy
+    Strategy(String, Vec<(String, Value)>),
+    Traversal(Vec<Step>),
+}
+
+pub fn parse_query(query: &str) -> Result<Query, ParseError> {
+    Parser::new(query, false).parse()
+}
+
+// $name placeholders are accepted as Value::Variable
+pub fn parse_query_with_variables(query: &str) -> Result<Query, ParseError> {
+    Parser::new(query, true).parse()
+}
+
+const TERMINAL_STEPS: [&str; 6] = ["explain", "hasNext", "iterate", "next", "toList", "toSet"];
+
+// gremlin names of the process enums. bare tokens resolve in this order, WithOptions last as
+// its names clash with Column, Pick, Pop and Cardinality tokens
+pub const TOKENS: [(&str, &[&str]); 13] = [
+    ("T", &["id", "key", "label", "value"]),
+    ("Order", &["asc", "desc", "shuffle"]),
+    ("Scope", &["global", "local"]),
+    ("Cardinality", &["list", "set", "single"]),
+    ("Direction", &["BOTH", "IN", "OUT"]),
+    ("Pop", &["all", "first", "last", "mixed"]),
+    ("Pick", &["none", "any"]),
+    ("Merge", &["onCreate", "onMatch"]),
+    (
+        "Operator",
+        &[
+            "sum", "minus", "mult", "div", "min", "max", "assign", "and", "or", "addAll", "sumLong",
+        ],
+    ),
+    ("Column", &["keys", "values"]),
+    ("Barrier", &["normSack"]),
+    ("DT", &["second", "minute", "hour", "day"]),
+    (
+        "WithOptions",
+        &[
+            "tokens", "indexer", "none", "ids", "labels", "keys", "values", "all", "list", "map",
+        ],
+    ),
+];
+
+const PREDICATES: [&str; 11] = [
+    "eq", "neq", "lt", "lte", "gt", "gte", "inside", "outside", "between", "within", "without",
+];
+
+const TEXT_PREDICATES: [&str; 8] = [
+    "startingWith",
+    "endingWith",
+    "containing",
+    "notStartingWith",
+    "notEndingWith",
+    "notContaining",
+    "regex",
+    "notRegex",
+];
+
+// builder methods taking no arguments, every other step takes a single argument or a tuple
+pub const NO_ARG_STEPS: [&str; 34] = [
+    "bothV",
+    "connectedComponent",
+    "count",
+    "cyclicPath",
+    "drop",
+    "element",
+    "fail",
+    "fold",
+    "id",
+    "identity",
+    "inV",
+    "index",
+    "key",
+    "label",
+    "loops",
+    "max",
+    "mean",
+    "min",
+    "none",
+    "order",
+    "otherV",
+    "outV",
+    "pageRank",
+    "path",
+    "peerPressure",
+    "profile",
+    "read",
+    "shortestPath",
+    "simplePath",
+    "sum",
+    "unfold",
+    "value",
+    "write",
+    "withPath",
+];
+
+// step arguments the builder takes as an i32, by step and position. an integer literal outside
+// the i32 range parses as a Long, which the builder can't take there
+pub const I32_ARGS: [(&str, usize); 4] =
+    [("barrier", 0), ("sample", 0), ("sample", 1), ("times", 0)];
+
+// the builder method of a step, suffixed with _ where the step is a rust keyword
+pub fn builder_method(op: &str) -> String {
+    match op {
+        "as" | "in" | "match" | "where" => format!("{}_", op),
+        op => op.to_string(),
+    }
+}
+
+fn token(class: &str, name: &str) -> Option<Value> {
+    let (class, names) = TOKENS.iter().find(|(c, _)| *c == class)?;
+    let name = names.iter().find(|n| **n == name)?;
+    Some(Value::Token(class, name))
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+    variables: bool,
+}
+
+impl Parser {
+    fn new(query: &str, variables: bool) -> Self {
+        Self {
+            chars: query.chars().collect(),
+            pos: 0,
+            variables,
+        }
+    }
+
+    fn parse(mut self) -> Result<Query, ParseError> {
+        let query = self.query()?;
+        self.eat(';');
+        self.skip_ws();
+        match self.peek() {
+            None => Ok(query),
+            Some(_) => Err(self.invalid("end of query")),
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn skip_ws(&mut self) {
+        while matches!(self.peek(), Some(c) if c.is_whitespace()) {
+            self.pos += 1;
+        }
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        self.skip_ws();
+        if self.peek() == Some(c) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, c: char, expected: &'static str) -> Result<(), ParseError> {
+        if self.eat(c) {
+            Ok(())
+        } else {
+            Err(self.invalid(expected))
+        }
+    }
+
+    fn invalid(&self, expected: &'static str) -> ParseError {
+        match self.peek() {
+            Some(c) => InvalidSyntax(self.pos, expected, c.to_string()),
+            None => Eof(expected),
+        }
+    }
+
+    fn ident(&mut self) -> Result<String, ParseError> {
+        self.skip_ws();
+        let start = self.pos;
+        match self.peek() {
+            Some(c) if c.is_alphabetic() || c == '_' => (),
+            _ => return Err(self.invalid("identifier")),
+        }
+        while matches!(self.peek(), Some(c) if c.is_alphanumeric() || c == '_') {
+            self.pos += 1;
+        }
+        Ok(self.chars[start..self.pos].iter().collect())
+    }
+
+    fn next_is(&mut self, c: char) -> bool {
+        self.skip_ws();
+        self.peek() == Some(c)
+    }
+
+    fn query(&mut self) -> Result<Query, ParseError> {
+        let alias = self.ident()?;
+        let mut query = Query {
+            source: None,
+            configuration: vec![],
+            steps: vec![],
+        };
+        if alias == "__" {
+            self.expect('.', ".")?;
+            let (pos, op) = (self.pos, self.ident()?);
+            query.steps = self.steps(pos, op)?;
+            return Ok(query);
+        }
+
+        query.source = Some(alias);
+        loop {
+            self.expect('.', ".")?;
+            let (pos, op) = (self.pos, self.ident()?);
+            if op.starts_with("with") {
+                let args = self.args()?;
+                query.configuration.push(Step { pos, op, args });
+            } else {
+                query.steps = self.steps(pos, op)?;
+                return Ok(query);
+            }
+        }
+    }
+
+    // a chain of steps starting with `op`, whose arguments have not been parsed yet
+    fn steps(&mut self, mut pos: usize, mut op: String) -> Result<Vec<Step>, ParseError> {
+        let mut steps = Vec::new();
+        loop {
+            let args = self.args()?;
+            if TERMINAL_STEPS.contains(&op.as_str()) {
+                return Ok(steps);
+            }
+            steps.push(Step { pos, op, args });
+            if !self.eat('.') {
+                return Ok(steps);
+            }
+            pos = self.pos;
+            op = self.ident()?;
+        }
+    }
+
+    fn args(&mut self) -> Result<Vec<Value>, ParseError> {
+        self.expect('(', "(")?;
+        let mut args = Vec::new();
+        if self.eat(')') {
+            return Ok(args);
+        }
+        loop {
+            args.push(self.value()?);
+            if !self.eat(',') {
+                self.expect(')', ", or )")?;
+                return Ok(args);
+            }
+        }
+    }
+
+    fn value(&mut self) -> Result<Value, ParseError> {
+        self.skip_ws();
+        match self.peek() {
+            None => Err(Eof("argument")),
+            Some('\'' | '"') => self.string().map(Value::Str),
+            Some(c) if c.is_ascii_digit() || c == '-' => self.number(),
+            Some('[') => self.collection(),
+            Some('$') if self.variables => self.variable(),
+            Some(_) => self.term(),
+        }
+    }
+
+    fn variable(&mut self) -> Result<Value, ParseError> {
+        self.expect('$', "$")?;
+        self.ident().map(Value::Variable)
+    }
+
+    fn string(&mut self) -> Result<String, ParseError> {
+        self.skip_ws();
+        let quote = match self.peek() {
+            Some(c @ ('\'' | '"')) => c,
+            _ => return Err(self.invalid("string")),
+        };
+        self.pos += 1;
+        let mut s = String::new();
+        loop {
+            let c = self.peek().ok_or(Eof("closing quote"))?;
+            self.pos += 1;
+            match c {
+                c if c == quote => return Ok(s),
+                '\\' => {
+                    let e = self.peek().ok_or(Eof("escaped character"))?;
+                    self.pos += 1;
+                    s.push(match e {
+                        'n' => '\n',
+                        'r' => '\r',
+                        't' => '\t',
+                        e => e,
+                    });
+                }
+                c => s.push(c),
+            }
+        }
+    }
+
+    fn number(&mut self) -> Result<Value, ParseError> {
+        let start = self.pos;
+        if self.peek() == Some('-') {
+            self.pos += 1;
+        }
+        let mut float = false;
+        loop {
+            match self.peek() {
+                Some(c) if c.is_ascii_digit() => (),
+                Some('.') if matches!(self.chars.get(self.pos + 1), Some(c) if c.is_ascii_digit()) => {
+                    float = true
+                }
+                Some('e' | 'E') => {
+                    float = true;
+                    if matches!(self.chars.get(self.pos + 1), Some('-' | '+')) {
+                        self.pos += 1;
+                    }
+                }
+                _ => break,
+            }
+            self.pos += 1;
+        }
+        let text: String = self.chars[start..self.pos].iter().collect();
+        let invalid = || InvalidSyntax(start, "number", text.clone());
+
+        let suffix = match self.peek() {
+            Some(c @ ('l' | 'L' | 'i' | 'I' | 'd' | 'D' | 'f' | 'F')) => {
+                self.pos += 1;
+                Some(c.to_ascii_lowercase())
+            }
+            _ => None,
+        };
+        let value = match (suffix, float) {
+            (Some('l'), false) => text.parse().map(Number::Long).ok(),
+            (Some('i'), false) => text.parse().map(Number::Integer).ok(),
+            (Some('f'), _) => text.parse().map(Number::Float).ok(),
+            (Some('d'), _) | (None, true) => text.parse().map(Number::Double).ok(),
+            (None, false) => match text.parse() {
+                Ok(i) => Some(Number::Integer(i)),
+                Err(_) => text.parse().map(Number::Long).ok(),
+            },
+            _ => None,
+        };
+        value
+            .map(|value| Value::Number {
+                pos: start,
+                value,
+                suffixed: suffix.is_some(),
+            })
+            .ok_or_else(invalid)
+    }
+
+    // [a, b] lists and [k: v] / [:] maps, map keys are strings, bare identifiers or
+    // parenthesized values such as (T.label)
+    fn collection(&mut self) -> Result<Value, ParseError> {
+        self.expect('[', "[")?;
+        if self.eat(']') {
+            return Ok(Value::List(vec![]));
+        }
+        if self.eat(':') {
+            self.expect(']', "]")?;
+            return Ok(Value::Map(vec![]));
+        }
+
+        let mut list = Vec::new();
+        let mut map = Vec::new();
+        loop {
+            let start = self.pos;
+            let key = match self.peek_map_key()? {
+                Some(k) => k,
+                None => {
+                    if !map.is_empty() {
+                        return Err(self.invalid("map key"));
+                    }
+                    list.push(self.value()?);
+                    if !self.eat(',') {
+                        self.expect(']', ", or ]")?;
+                        return Ok(Value::List(list));
+                    }
+                    continue;
+                }
+            };
+            if !list.is_empty() {
+                self.pos = start;
+                return Err(self.invalid("list element"));
+            }
+            map.push((key, self.value()?));
+            if !self.eat(',') {
+                self.expect(']', ", or ]")?;
+                return Ok(Value::Map(map));
+            }
+        }
+    }
+
+    // consumes `key:` when the next element is a map entry
+    fn peek_map_key(&mut self) -> Result<Option<Value>, ParseError> {
+        self.skip_ws();
+        let start = self.pos;
+        let key = match self.peek() {
+            Some('\'' | '"') => Value::Str(self.string()?),
+            Some(c) if c.is_alphabetic() || c == '_' => Value::Str(self.ident()?),
+            Some('(') => {
+                self.pos += 1;
+                let key = self.value()?;
+                self.expect(')', ")")?;
+                key
+            }
+            _ => return Ok(None),
+        };
+        if self.eat(':') {
+            Ok(Some(key))
+        } else {
+            self.pos = start;
+            Ok(None)
+        }
+    }
+
+    // identifiers: booleans, null, tokens, predicates, strategies and nested traversals
+    fn term(&mut self) -> Result<Value, ParseError> {
+        let start = self.pos;
+        let name = self.ident()?;
+        match name.as_str() {
+            "true" => return Ok(Value::Bool(true)),
+            "false" => return Ok(Value::Bool(false)),
+            "null" => return Ok(Value::Null),
+            "new" => return self.strategy(),
+            "__" => {
+                self.expect('.', ".")?;
+                let (pos, op) = (self.pos, self.ident()?);
+                return self.steps(pos, op).map(Value::Traversal);
+            }
+            "P" | "TextP" => {
+                self.expect('.', ".")?;
+                let start = self.pos;
+                let op = self.ident()?;
+                let class = if name == "P" { "P" } else { "TextP" };
+                return self.predicate(start, class, op);
+            }
+            _ => (),
+        }
+
+        // VertexProperty.Cardinality.single
+        let name = if name == "VertexProperty" {
+            self.expect('.', ".")?;
+            self.ident()?
+        } else {
+            name
+        };
+        if TOKENS.iter().any(|(class, _)| *class == name) && self.eat('.') {
+            let start = self.pos;
+            let t = self.ident()?;
+            return token(&name, &t).ok_or(Unknown(start, "token", format!("{}.{}", name, t)));
+        }
+        if self.next_is('(') {
+            if PREDICATES.contains(&name.as_str()) {
+                return self.predicate(start, "P", name);
+            }
+            if TEXT_PREDICATES.contains(&name.as_str()) {
+                return self.predicate(start, "TextP", name);
+            }
+            return self.steps(start, name).map(Value::Traversal);
+        }
+        if name.ends_with("Strategy") {
+            return Ok(Value::Strategy(name, vec![]));
+        }
+        TOKENS
+            .iter()
+            .find_map(|(class, _)| token(class, &name))
+            .ok_or(Unknown(start, "token", name))
+    }
+
+    // the predicate and the and, or, negate steps chained onto it
+    fn predicate(
+        &mut self,
+        pos: usize,
+        class: &'static str,
+        op: String,
+    ) -> Result<Value, ParseError> {
+        let args = self.args()?;
+        let mut modifiers = Vec::new();
+        while self.eat('.') {
+            let pos = self.pos;
+            let op = self.ident()?;
+            let args = self.args()?;
+            match (op.as_str(), args.len()) {
+                ("negate", 0) | ("and" | "or", 1) => modifiers.push(Step { pos, op, args }),
+                _ => return Err(Unknown(pos, "predicate modifier", op)),
+            }
+        }
+        Ok(Value::Predicate {
+            pos,
+            class,
+            op,
+            args,
+            modifiers,
+        })
+    }
+
+    // new SubgraphStrategy(vertices: __.hasLabel('person'), checkAdjacentVertices: false)
+    fn strategy(&mut self) -> Result<Value, ParseError> {
+        let start = self.pos;
+        let name = self.ident()?;
+        if !name.ends_with("Strategy") {
+            return Err(Unknown(start, "strategy", name));
+        }
+        self.expect('(', "(")?;
+        let mut configuration = Vec::new();
+        if self.eat(')') {
+            return Ok(Value::Strategy(name, configuration));
+        }
+        loop {
+            let key = self.ident()?;
+            self.expect(':', ":")?;
+            configuration.push((key, self.value()?));
+            if !self.eat(',') {
+                self.expect(')', ", or )")?;
+                return Ok(Value::Strategy(name, configuration));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn syntax_tree() {
+        let query =
+            parse_query("g.withBulk(false).V().has('age', P.gt(30).negate()).toList()").unwrap();
+        assert_eq!(query.source.as_deref(), Some("g"));
+        assert_eq!(query.configuration[0].args, [Value::Bool(false)]);
+        assert_eq!(query.steps.len(), 2);
+        assert_eq!(
+            query.steps[1].args[1],
+            Value::Predicate {
+                pos: 35,
+                class: "P",
+                op: "gt".to_string(),
+                args: vec![Value::Number {
+                    pos: 38,
+                    value: Number::Integer(30),
+                    suffixed: false
+                }],
+                modifiers: vec![Step {
+                    pos: 42,
+                    op: "negate".to_string(),
+                    args: vec![]
+                }],
+            }
+        );
+
+        let query = parse_query_with_variables("__.out($label).times(3000000000)").unwrap();
+        assert_eq!(query.source, None);
+        assert_eq!(query.steps[0].args, [Value::Variable("label".to_string())]);
+        assert!(matches!(
+            query.steps[1].args[0],
+            Value::Number {
+                pos: 21,
+                value: Number::Long(3000000000),
+                suffixed: false
+            }
+        ));
+        assert_eq!(
+            parse_query("__.out($label)").unwrap_err(),
+            InvalidSyntax(7, "identifier", "$".to_string())
+        );
+    }
+}
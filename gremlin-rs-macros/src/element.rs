@@ -0,0 +1,410 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Error, Fields, Ident, LitStr, Type};
+
+// #[gremlin(label = "person")] on the struct, on fields #[gremlin(id)], #[gremlin(skip)],
+// #[gremlin(name = "firstName")] and #[gremlin(cardinality = "list" | "set" | "single")], edges
+// mark their endpoint vertex ids with #[gremlin(out)] and #[gremlin(in)].
+// Vec and set fields default to list and set cardinality (edge properties are always single),
+// Option fields are left out when None
+
+enum Cardinality {
+    Single,
+    List,
+    Set,
+}
+
+enum Role {
+    Id,
+    Out,
+    In,
+    Property(Cardinality),
+    Skip,
+}
+
+struct Field {
+    ident: Ident,
+    key: String,
+    role: Role,
+    optional: bool,
+    collection: bool,
+}
+
+pub struct Element {
+    ident: Ident,
+    label: String,
+    fields: Vec<Field>,
+}
+
+// the last path segment of a field's type, e.g. Option for Option<String>
+fn type_name(ty: &Type) -> Option<String> {
+    match ty {
+        Type::Path(p) => p.path.segments.last().map(|s| s.ident.to_string()),
+        _ => None,
+    }
+}
+
+impl Element {
+    pub fn parse(input: &DeriveInput) -> Result<Self, Error> {
+        let mut label = input.ident.to_string();
+        for attr in input.attrs.iter().filter(|a| a.path().is_ident("gremlin")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("label") {
+                    label = meta.value()?.parse::<LitStr>()?.value();
+                    Ok(())
+                } else {
+                    Err(meta.error("expected label = \"...\""))
+                }
+            })?;
+        }
+
+        let named = match &input.data {
+            Data::Struct(s) => match &s.fields {
+                Fields::Named(named) => &named.named,
+                _ => return Err(Error::new_spanned(&input.ident, "expected named fields")),
+            },
+            _ => return Err(Error::new_spanned(&input.ident, "expected a struct")),
+        };
+
+        let mut fields = Vec::new();
+        for f in named {
+            let ident = f.ident.clone().expect("named field");
+            let ty = type_name(&f.ty);
+            let mut key = ident.to_string();
+            let mut role = Role::Property(match ty.as_deref() {
+                Some("Vec") => Cardinality::List,
+                Some("HashSet" | "BTreeSet") => Cardinality::Set,
+                _ => Cardinality::Single,
+            });
+            for attr in f.attrs.iter().filter(|a| a.path().is_ident("gremlin")) {
+                attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("id") {
+                        role = Role::Id;
+                    } else if meta.path.is_ident("out") {
+                        role = Role::Out;
+                    } else if meta.path.is_ident("in") {
+                        role = Role::In;
+                    } else if meta.path.is_ident("skip") {
+                        role = Role::Skip;
+                    } else if meta.path.is_ident("name") {
+                        key = meta.value()?.parse::<LitStr>()?.value();
+                    } else if meta.path.is_ident("cardinality") {
+                        let value = meta.value()?.parse::<LitStr>()?;
+                        role = Role::Property(match value.value().as_str() {
+                            "single" => Cardinality::Single,
+                            "list" => Cardinality::List,
+                            "set" => Cardinality::Set,
+                            _ => {
+                                return Err(Error::new_spanned(
+                                    value,
+                                    "expected single, list or set",
+                                ))
+                            }
+                        });
+                    } else {
+                        return Err(meta.error("expected id, out, in, skip, name or cardinality"));
+                    }
+                    Ok(())
+                })?;
+            }
+            fields.push(Field {
+                ident,
+                key,
+                role,
+                optional: ty.as_deref() == Some("Option"),
+                collection: matches!(ty.as_deref(), Some("Vec" | "HashSet" | "BTreeSet")),
+            });
+        }
+
+        Ok(Self {
+            ident: input.ident.clone(),
+            label,
+            fields,
+        })
+    }
+
+    // the field with the given role, at most one field may have it
+    fn field(&self, role: fn(&Role) -> bool, attr: &str) -> Result<Option<&Field>, Error> {
+        let mut fields = self.fields.iter().filter(|f| role(&f.role));
+        match (fields.next(), fields.next()) {
+            (f, None) => Ok(f),
+            _ => Err(Error::new_spanned(
+                &self.ident,
+                format!("expected one #[gremlin({})] field", attr),
+            )),
+        }
+    }
+
+    fn required(&self, role: fn(&Role) -> bool, attr: &str) -> Result<&Field, Error> {
+        self.field(role, attr)?.ok_or_else(|| {
+            Error::new_spanned(
+                &self.ident,
+                format!("expected one #[gremlin({})] field", attr),
+            )
+        })
+    }
+
+    // the element id when there is one, and the id property step for addV/addE
+    fn id_steps(&self, id: Option<&Field>) -> (TokenStream, TokenStream) {
+        match id {
+            Some(f) if f.optional => {
+                let ident = &f.ident;
+                (
+                    quote!(self.#ident.clone().map(::std::convert::Into::into)),
+                    quote!(if let Some(id) = self.#ident.clone() {
+                        t = t.property((::gremlin_rs::process::T::Id, id));
+                    }),
+                )
+            }
+            Some(f) => {
+                let ident = &f.ident;
+                (
+                    quote!(Some(self.#ident.clone().into())),
+                    quote!(t = t.property((::gremlin_rs::process::T::Id, self.#ident.clone()));),
+                )
+            }
+            None => (quote!(None), quote!()),
+        }
+    }
+
+    fn properties(&self) -> impl Iterator<Item = (&Field, &Cardinality)> {
+        self.fields.iter().filter_map(|f| match &f.role {
+            Role::Property(c) => Some((f, c)),
+            _ => None,
+        })
+    }
+
+    // property steps appending every mapped property to `t`, `update` replaces existing values
+    fn property_steps(&self, update: bool, edge: bool) -> TokenStream {
+        let steps = self.properties().map(|(f, cardinality)| {
+            let (ident, key) = (&f.ident, &f.key);
+            let multi = match cardinality {
+                _ if edge => None,
+                Cardinality::List => Some(quote!(::gremlin_rs::process::Cardinality::List)),
+                Cardinality::Set => Some(quote!(::gremlin_rs::process::Cardinality::Set)),
+                Cardinality::Single => None,
+            };
+            let drop = quote!(t = t.sideEffect(::gremlin_rs::process::__.properties(#key).drop()););
+            let single = match update && !edge {
+                true => quote!((::gremlin_rs::process::Cardinality::Single, #key, v)),
+                false => quote!((#key, v)),
+            };
+            match (multi, f.optional) {
+                (Some(cardinality), _) => {
+                    let drop = if update { drop } else { quote!() };
+                    quote! {
+                        #drop
+                        for v in self.#ident.iter().cloned() {
+                            t = t.property((#cardinality, #key, v));
+                        }
+                    }
+                }
+                (None, true) => {
+                    let none = if update { drop } else { quote!() };
+                    quote! {
+                        match self.#ident.clone() {
+                            Some(v) => t = t.property(#single),
+                            None => { #none }
+                        }
+                    }
+                }
+                // a single property holding the whole collection
+                (None, false) if f.collection => quote! {
+                    let v = ::gremlin_rs::structure::gson::GsonV2::List(
+                        self.#ident.iter().cloned().map(::std::convert::Into::into).collect(),
+                    );
+                    t = t.property(#single);
+                },
+                (None, false) => quote! {
+                    let v = self.#ident.clone();
+                    t = t.property(#single);
+                },
+            }
+        });
+        quote!(#(#steps)*)
+    }
+
+    // `name: property(&mut map, "key", multi)?` for every field, skipped fields are defaulted
+    fn map_fields(&self, edge: bool) -> TokenStream {
+        let fields = self.fields.iter().map(|f| {
+            let ident = &f.ident;
+            let (key, multi) = match &f.role {
+                Role::Skip => return quote!(#ident: ::std::default::Default::default()),
+                Role::Out => {
+                    return quote!(#ident: ::gremlin_rs::process::endpoint(&mut map, "OUT")?)
+                }
+                Role::In => return quote!(#ident: ::gremlin_rs::process::endpoint(&mut map, "IN")?),
+                Role::Id => ("id", false),
+                Role::Property(_) if edge => (f.key.as_str(), false),
+                Role::Property(Cardinality::Single) => (f.key.as_str(), false),
+                Role::Property(_) => (f.key.as_str(), true),
+            };
+            quote!(#ident: ::gremlin_rs::process::property(&mut map, #key, #multi)?)
+        });
+        quote!(#(#fields),*)
+    }
+
+    fn deserialize(&self, from_map: TokenStream) -> TokenStream {
+        let ident = &self.ident;
+        quote! {
+            impl<'de> ::gremlin_rs::__private::serde::Deserialize<'de> for #ident {
+                fn deserialize<D>(deserializer: D) -> ::std::result::Result<Self, D::Error>
+                where
+                    D: ::gremlin_rs::__private::serde::Deserializer<'de>,
+                {
+                    ::gremlin_rs::process::deserialize_element(deserializer, #from_map)
+                }
+            }
+        }
+    }
+
+    pub fn vertex(&self) -> Result<TokenStream, Error> {
+        let (ident, label) = (&self.ident, &self.label);
+        if let Some(f) = self
+            .fields
+            .iter()
+            .find(|f| matches!(f.role, Role::Out | Role::In))
+        {
+            return Err(Error::new_spanned(
+                &f.ident,
+                "vertices have no out or in field",
+            ));
+        }
+        let id = self.required(|r| matches!(r, Role::Id), "id")?;
+        let (vertex_id, add_id) = self.id_steps(Some(id));
+        let add_properties = self.property_steps(false, false);
+        let update_properties = self.property_steps(true, false);
+        let fields = self.map_fields(false);
+        let deserialize = self.deserialize(quote!(
+            <Self as ::gremlin_rs::process::GremlinVertex>::from_map
+        ));
+
+        Ok(quote! {
+            impl ::gremlin_rs::process::GremlinVertex for #ident {
+                const LABEL: &'static str = #label;
+
+                fn vertex_id(&self) -> ::std::option::Option<::gremlin_rs::structure::gson::GsonV2> {
+                    #vertex_id
+                }
+
+                fn add_vertex(
+                    &self,
+                    source: &::gremlin_rs::process::TraversalSource,
+                ) -> ::gremlin_rs::process::Traversal<
+                    ::gremlin_rs::structure::Vertex,
+                    ::gremlin_rs::structure::Vertex,
+                > {
+                    #[allow(unused_mut)]
+                    let mut t = source.addV(#label);
+                    #add_id
+                    #add_properties
+                    t
+                }
+
+                fn update_properties(
+                    &self,
+                    t: ::gremlin_rs::process::Traversal<
+                        ::gremlin_rs::structure::Vertex,
+                        ::gremlin_rs::structure::Vertex,
+                    >,
+                ) -> ::gremlin_rs::process::Traversal<
+                    ::gremlin_rs::structure::Vertex,
+                    ::gremlin_rs::structure::Vertex,
+                > {
+                    #[allow(unused_mut)]
+                    let mut t = t;
+                    #update_properties
+                    t
+                }
+
+                fn from_map(
+                    mut map: ::std::collections::HashMap<
+                        ::std::string::String,
+                        ::gremlin_rs::structure::gson::GsonV2,
+                    >,
+                ) -> ::std::result::Result<Self, ::gremlin_rs::structure::de::GsonError> {
+                    Ok(Self { #fields })
+                }
+            }
+
+            #deserialize
+        })
+    }
+
+    pub fn edge(&self) -> Result<TokenStream, Error> {
+        let (ident, label) = (&self.ident, &self.label);
+        let out_v = &self.required(|r| matches!(r, Role::Out), "out")?.ident;
+        let in_v = &self.required(|r| matches!(r, Role::In), "in")?.ident;
+        let id = self.field(|r| matches!(r, Role::Id), "id")?;
+        let (edge_id, add_id) = self.id_steps(id);
+        // edges are found from their out vertex, by id or else by the in vertex
+        let find_edge = match id {
+            Some(f) if f.optional => quote!(.hasId(
+                ::gremlin_rs::process::GremlinEdge::edge_id(self)
+                    .unwrap_or(::gremlin_rs::structure::gson::GsonV2::Null)
+            )),
+            Some(f) => {
+                let id = &f.ident;
+                quote!(.hasId(self.#id.clone()))
+            }
+            None => quote!(.where_(::gremlin_rs::process::__.inV().hasId(self.#in_v.clone()))),
+        };
+        let add_properties = self.property_steps(false, true);
+        let update_properties = self.property_steps(true, true);
+        let fields = self.map_fields(true);
+        let deserialize = self.deserialize(quote!(
+            <Self as ::gremlin_rs::process::GremlinEdge>::from_map
+        ));
+
+        Ok(quote! {
+            impl ::gremlin_rs::process::GremlinEdge for #ident {
+                const LABEL: &'static str = #label;
+
+                fn edge_id(&self) -> ::std::option::Option<::gremlin_rs::structure::gson::GsonV2> {
+                    #edge_id
+                }
+
+                fn add_edge(
+                    &self,
+                    source: &::gremlin_rs::process::TraversalSource,
+                ) -> ::gremlin_rs::process::Traversal<
+                    ::gremlin_rs::structure::Edge,
+                    ::gremlin_rs::structure::Edge,
+                > {
+                    #[allow(unused_mut)]
+                    let mut t = source
+                        .addE(#label)
+                        .from(::gremlin_rs::process::__.V(self.#out_v.clone()))
+                        .to(::gremlin_rs::process::__.V(self.#in_v.clone()));
+                    #add_id
+                    #add_properties
+                    t
+                }
+
+                fn update_edge(
+                    &self,
+                    source: &::gremlin_rs::process::TraversalSource,
+                ) -> ::gremlin_rs::process::Traversal<
+                    ::gremlin_rs::structure::Vertex,
+                    ::gremlin_rs::structure::Edge,
+                > {
+                    #[allow(unused_mut)]
+                    let mut t = source.V(self.#out_v.clone()).outE(#label)#find_edge;
+                    #update_properties
+                    t
+                }
+
+                fn from_map(
+                    mut map: ::std::collections::HashMap<
+                        ::std::string::String,
+                        ::gremlin_rs::structure::gson::GsonV2,
+                    >,
+                ) -> ::std::result::Result<Self, ::gremlin_rs::structure::de::GsonError> {
+                    Ok(Self { #fields })
+                }
+            }
+
+            #deserialize
+        })
+    }
+}
@@ -0,0 +1,322 @@
+use gremlin_rs_grammar::{
+    builder_method, parse_query_with_variables, Number, ParseError, Step, Value, I32_ARGS,
+    NO_ARG_STEPS,
+};
+use proc_macro2::{Ident, Literal, Span, TokenStream};
+use quote::quote;
+
+// lowers the syntax tree of gremlin_rs_grammar, which gremlin_rs::process::parser lowers to
+// bytecode, to the builder calls of the Translator::Rust form, $name placeholders become the
+// rust variable name
+
+use ParseError::*;
+
+pub fn parse_query(query: &str) -> Result<TokenStream, ParseError> {
+    let query = parse_query_with_variables(query)?;
+    let mut traversal = match query.source {
+        Some(alias) => {
+            let alias = ident(&alias);
+            quote!(#alias)
+        }
+        None => quote!(::gremlin_rs::process::__),
+    };
+    for step in query.configuration.iter().chain(query.steps.iter()) {
+        traversal.extend(self::step(step)?);
+    }
+    Ok(traversal)
+}
+
+fn ident(name: &str) -> Ident {
+    Ident::new(name, Span::call_site())
+}
+
+// the rust variants are the gremlin names capitalised
+fn token(class: &str, name: &str) -> TokenStream {
+    let (first, rest) = name.split_at(1);
+    let rest = match class {
+        "Direction" => rest.to_ascii_lowercase(),
+        _ => rest.to_string(),
+    };
+    let variant = ident(&format!("{}{}", first.to_ascii_uppercase(), rest));
+    let class = ident(class);
+    quote!(::gremlin_rs::process::#class::#variant)
+}
+
+// plain integers are left unsuffixed so the step's argument type picks i32 or i64
+fn number(value: Number, suffixed: bool) -> Literal {
+    match (value, suffixed) {
+        (Number::Integer(i), false) => Literal::i32_unsuffixed(i),
+        (Number::Integer(i), true) => Literal::i32_suffixed(i),
+        (Number::Long(l), _) => Literal::i64_suffixed(l),
+        (Number::Float(f), _) => Literal::f32_suffixed(f),
+        (Number::Double(d), false) => Literal::f64_unsuffixed(d),
+        (Number::Double(d), true) => Literal::f64_suffixed(d),
+    }
+}
+
+fn tokens(value: &Value) -> Result<TokenStream, ParseError> {
+    Ok(match value {
+        Value::Str(s) => quote!(#s),
+        Value::Number {
+            value,
+            suffixed,
+            pos,
+        } => match value {
+            Number::Float(f) if !f.is_finite() => {
+                return Err(InvalidSyntax(*pos, "finite number", f.to_string()))
+            }
+            Number::Double(d) if !d.is_finite() => {
+                return Err(InvalidSyntax(*pos, "finite number", d.to_string()))
+            }
+            value => {
+                let literal = number(*value, *suffixed);
+                quote!(#literal)
+            }
+        },
+        Value::Bool(b) => quote!(#b),
+        Value::Null => quote!(::gremlin_rs::structure::gson::GsonV2::Null),
+        Value::Variable(v) => {
+            let v = ident(v);
+            quote!(#v)
+        }
+        Value::List(l) => {
+            let values = l.iter().map(gson).collect::<Result<Vec<_>, _>>()?;
+            quote!(::gremlin_rs::structure::gson::GsonV2::List(
+                vec![#(#values),*]
+            ))
+        }
+        Value::Map(entries) => gson_value_map(entries)?,
+        Value::Token(class, name) => token(class, name),
+        Value::Predicate {
+            pos,
+            class,
+            op,
+            args,
+            modifiers,
+        } => predicate(*pos, class, op, args, modifiers)?,
+        Value::Strategy(name, configuration) if configuration.is_empty() => {
+            quote!(::gremlin_rs::process::TraversalStrategy::new(#name))
+        }
+        Value::Strategy(name, configuration) => {
+            let configuration = gson_map(configuration)?;
+            quote!(::gremlin_rs::process::TraversalStrategy {
+                name: #name.to_string(),
+                configuration: #configuration,
+            })
+        }
+        Value::Traversal(steps) => {
+            let mut traversal = quote!(::gremlin_rs::process::__);
+            for step in steps {
+                traversal.extend(self::step(step)?);
+            }
+            traversal
+        }
+    })
+}
+
+fn gson(value: &Value) -> Result<TokenStream, ParseError> {
+    let tokens = tokens(value)?;
+    Ok(quote!(::gremlin_rs::structure::gson::GsonV2::from(#tokens)))
+}
+
+fn tuple(args: &[Value]) -> Result<TokenStream, ParseError> {
+    let args = args.iter().map(tokens).collect::<Result<Vec<_>, _>>()?;
+    Ok(match args.as_slice() {
+        [] => quote!(()),
+        [arg] => arg.clone(),
+        args => quote!((#(#args),*)),
+    })
+}
+
+// a long or floating point literal where the builder takes an i32 would only fail to type check
+fn check_i32_args(step: &Step) -> Result<(), ParseError> {
+    for (i, arg) in step.args.iter().enumerate() {
+        if let Value::Number {
+            pos,
+            value: value @ (Number::Long(_) | Number::Float(_) | Number::Double(_)),
+            ..
+        } = arg
+        {
+            if I32_ARGS.contains(&(step.op.as_str(), i)) {
+                return Err(InvalidSyntax(*pos, "an i32", value.to_string()));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn step(step: &Step) -> Result<TokenStream, ParseError> {
+    check_i32_args(step)?;
+    let name = ident(&builder_method(&step.op));
+    Ok(match (step.op.as_str(), step.args.as_slice()) {
+        (op, []) if NO_ARG_STEPS.contains(&op) => quote!(.#name()),
+        ("toE", [direction, labels @ ..]) => {
+            let direction = tokens(direction)?;
+            let labels = tuple(labels)?;
+            quote!(.#name(#direction, #labels))
+        }
+        (_, args) => {
+            let args = tuple(args)?;
+            quote!(.#name(#args))
+        }
+    })
+}
+
+fn gson_map(entries: &[(String, Value)]) -> Result<TokenStream, ParseError> {
+    let entries = entries
+        .iter()
+        .map(|(k, v)| {
+            let v = gson(v)?;
+            Ok(quote!((#k.to_string(), #v)))
+        })
+        .collect::<Result<Vec<_>, ParseError>>()?;
+    Ok(quote!(::std::collections::HashMap::from([#(#entries),*])))
+}
+
+// maps keyed only by strings are GsonV2::Map values, any other key makes a GsonV2::GMap
+fn gson_value_map(entries: &[(Value, Value)]) -> Result<TokenStream, ParseError> {
+    let strings: Option<Vec<(String, &Value)>> = entries
+        .iter()
+        .map(|(k, v)| match k {
+            Value::Str(k) => Some((k.clone(), v)),
+            _ => None,
+        })
+        .collect();
+    if let Some(strings) = strings {
+        let entries = strings
+            .into_iter()
+            .map(|(k, v)| {
+                let v = gson(v)?;
+                Ok(quote!((#k.to_string(), #v)))
+            })
+            .collect::<Result<Vec<_>, ParseError>>()?;
+        return Ok(quote!(::gremlin_rs::structure::gson::GsonV2::Map(
+            ::std::collections::HashMap::from([#(#entries),*])
+        )));
+    }
+    let entries = entries
+        .iter()
+        .map(|(k, v)| {
+            let (k, v) = (gson(k)?, gson(v)?);
+            Ok(quote!(.with(#k, #v)))
+        })
+        .collect::<Result<Vec<_>, ParseError>>()?;
+    Ok(quote!(::gremlin_rs::structure::gson::GsonV2::GMap(
+        ::gremlin_rs::structure::gson::GsonMap::new()#(#entries)*
+    )))
+}
+
+fn predicate(
+    pos: usize,
+    class: &str,
+    op: &str,
+    args: &[Value],
+    modifiers: &[Step],
+) -> Result<TokenStream, ParseError> {
+    let unknown = || Unknown(pos, "predicate", format!("{}.{}", class, op));
+    let name = ident(op);
+    let mut p = match (class, op, args) {
+        ("P", "eq" | "neq" | "lt" | "lte" | "gt" | "gte" | "not", [v]) => {
+            let v = tokens(v)?;
+            quote!(::gremlin_rs::process::P::#name(#v))
+        }
+        ("P", "inside" | "outside" | "between", [a, b]) => {
+            let (a, b) = (tokens(a)?, tokens(b)?);
+            quote!(::gremlin_rs::process::P::#name(#a, #b))
+        }
+        ("P", "within" | "without", [Value::List(l)]) => within(&name, l)?,
+        // a single argument (a value or a rust collection) is passed through as is
+        ("P", "within" | "without", [v]) => {
+            let v = tokens(v)?;
+            quote!(::gremlin_rs::process::P::#name(#v))
+        }
+        ("P", "within" | "without", args) => within(&name, args)?,
+        ("TextP", _, [v @ (Value::Str(_) | Value::Variable(_))]) => {
+            let v = tokens(v)?;
+            quote!(::gremlin_rs::process::TextP::#name(#v))
+        }
+        _ => return Err(unknown()),
+    };
+
+    // and, or, negate chained onto the predicate
+    for step in modifiers {
+        let name = ident(&step.op);
+        p = match (step.op.as_str(), step.args.as_slice()) {
+            ("negate", []) => quote!(#p.negate()),
+            ("and" | "or", [other]) => {
+                let other = tokens(other)?;
+                quote!(#p.#name(#other))
+            }
+            _ => return Err(Unknown(step.pos, "predicate modifier", step.op.clone())),
+        };
+    }
+    Ok(p)
+}
+
+fn within(name: &Ident, values: &[Value]) -> Result<TokenStream, ParseError> {
+    let values = values.iter().map(gson).collect::<Result<Vec<_>, _>>()?;
+    Ok(quote!(::gremlin_rs::process::P::#name(
+        ::gremlin_rs::process::bytecode::BytecodeStep(vec![#(#values),*])
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expand(query: &str) -> String {
+        parse_query(query).unwrap().to_string().replace(' ', "")
+    }
+
+    #[test]
+    fn expansion() {
+        assert_eq!(
+            expand("g.V().has('email', $email).limit(1).count()"),
+            "g.V(()).has((\"email\",email)).limit(1).count()"
+        );
+        assert_eq!(
+            expand("g.V().both('a', 'b').order().by(T.id, desc)"),
+            "g.V(()).both((\"a\",\"b\")).order().by((::gremlin_rs::process::T::Id,::gremlin_rs::process::Order::Desc))"
+        );
+        assert_eq!(
+            expand("__.in().toE(IN, $label).limit(3000000000).times(3)"),
+            "::gremlin_rs::process::__.in_(()).toE(::gremlin_rs::process::Direction::In,label).limit(3000000000i64).times(3)"
+        );
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(parse_query("g.V().out(").unwrap_err(), Eof("argument"));
+        assert_eq!(parse_query("g").unwrap_err(), Eof("."));
+        assert_eq!(
+            parse_query("g.V().has('a', bogus)").unwrap_err(),
+            Unknown(15, "token", "bogus".to_string())
+        );
+        assert_eq!(
+            parse_query("g.V().has('a', $)").unwrap_err(),
+            InvalidSyntax(16, "identifier", ")".to_string())
+        );
+        assert_eq!(
+            parse_query("g.V().count() x").unwrap_err().to_string(),
+            "invalid syntax at 14: expected end of query - got x"
+        );
+        assert!(matches!(
+            parse_query("g.V().has('a', TextP.containing(1))"),
+            Err(Unknown(21, "predicate", _))
+        ));
+        assert_eq!(
+            parse_query("g.V().repeat(out()).times(3000000000)")
+                .unwrap_err()
+                .to_string(),
+            "invalid syntax at 26: expected an i32 - got 3000000000"
+        );
+        assert_eq!(
+            parse_query("g.V().sample(local, 2L)").unwrap_err(),
+            InvalidSyntax(20, "an i32", "2".to_string())
+        );
+        assert_eq!(
+            parse_query("__.barrier(1.5)").unwrap_err(),
+            InvalidSyntax(11, "an i32", "1.5".to_string())
+        );
+    }
+}
@@ -0,0 +1,39 @@
+use proc_macro::TokenStream;
+use syn::{parse_macro_input, DeriveInput, LitStr};
+
+mod element;
+mod gremlin;
+
+// gremlin!("g.V().hasLabel('user').has('email', $email)") parses the gremlin-groovy text at
+// compile time and expands to the builder calls, g.V(()).hasLabel("user").has(("email", email)).
+// the source (g above) and $name placeholders are resolved in the calling scope, syntax errors
+// and long or floating point literals for i32 arguments are reported as compile errors
+#[proc_macro]
+pub fn gremlin(input: TokenStream) -> TokenStream {
+    let query = parse_macro_input!(input as LitStr);
+    match gremlin::parse_query(&query.value()) {
+        Ok(traversal) => traversal.into(),
+        Err(e) => syn::Error::new(query.span(), e).to_compile_error().into(),
+    }
+}
+
+// implements process::GremlinVertex and a Deserialize reading valueMap(true)/elementMap() results,
+// see element.rs for the attributes
+#[proc_macro_derive(GremlinVertex, attributes(gremlin))]
+pub fn derive_vertex(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    element::Element::parse(&input)
+        .and_then(|e| e.vertex())
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+// implements process::GremlinEdge and a Deserialize reading elementMap() results
+#[proc_macro_derive(GremlinEdge, attributes(gremlin))]
+pub fn derive_edge(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    element::Element::parse(&input)
+        .and_then(|e| e.edge())
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
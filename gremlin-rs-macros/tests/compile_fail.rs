@@ -0,0 +1,6 @@
+// gremlin! queries the grammar or the builder signatures reject must not compile
+#[test]
+fn compile_fail() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+}
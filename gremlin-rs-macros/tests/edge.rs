@@ -0,0 +1,107 @@
+use gremlin_rs::{
+    process::{bytecode::Bytecode, *},
+    structure::de::from_str,
+};
+use serde_json::Value;
+
+#[derive(GremlinEdge, Debug, PartialEq)]
+#[gremlin(label = "knows")]
+struct Knows {
+    #[gremlin(out)]
+    person: i64,
+    #[gremlin(in)]
+    friend: i64,
+    weight: f64,
+    #[gremlin(name = "sinceYear")]
+    since: Option<i32>,
+}
+
+#[derive(GremlinEdge, Debug)]
+#[gremlin(label = "created")]
+struct Created {
+    #[gremlin(id)]
+    id: Option<i64>,
+    #[gremlin(out)]
+    author: i64,
+    #[gremlin(in)]
+    software: i64,
+    tags: Vec<String>,
+}
+
+fn steps<S, E>(t: Traversal<S, E>) -> Value {
+    let b: Bytecode = t.into();
+    serde_json::to_value(b).unwrap()
+}
+
+#[test]
+fn edge_traversals() {
+    let knows = Knows {
+        person: 1,
+        friend: 2,
+        weight: 0.5,
+        since: None,
+    };
+    assert_eq!(Knows::LABEL, "knows");
+    assert!(knows.edge_id().is_none());
+    assert_eq!(
+        steps(knows.add_edge(&g)),
+        steps(
+            g.addE("knows")
+                .from(__.V(1i64))
+                .to(__.V(2i64))
+                .property(("weight", 0.5))
+        )
+    );
+    assert_eq!(
+        steps(knows.update_edge(&g)),
+        steps(
+            g.V(1i64)
+                .outE("knows")
+                .where_(__.inV().hasId(2i64))
+                .property(("weight", 0.5))
+                .sideEffect(__.properties("sinceYear").drop())
+        )
+    );
+
+    let created = Created {
+        id: Some(9),
+        author: 1,
+        software: 3,
+        tags: vec!["a".to_string()],
+    };
+    assert_eq!(
+        steps(created.add_edge(&g)),
+        steps(
+            g.addE("created")
+                .from(__.V(1i64))
+                .to(__.V(3i64))
+                .property((T::Id, 9i64))
+                .property((
+                    "tags",
+                    gremlin_rs::structure::gson::GsonV2::List(vec!["a".into()])
+                ))
+        )
+    );
+    assert_eq!(
+        steps(created.update_edge(&g))["step"][2],
+        serde_json::json!(["hasId", {"@type": "g:Int64", "@value": 9}])
+    );
+}
+
+#[test]
+fn edge_deserialization() {
+    let element_map = r#"{"id":{"@type":"g:Int32","@value":7},"label":"knows","IN":{"id":{"@type":"g:Int64","@value":2},"label":"person"},"OUT":{"id":{"@type":"g:Int64","@value":1},"label":"person"},"weight":{"@type":"g:Double","@value":0.5},"sinceYear":{"@type":"g:Int32","@value":2010}}"#;
+    assert_eq!(
+        from_str::<Knows>(element_map).unwrap(),
+        Knows {
+            person: 1,
+            friend: 2,
+            weight: 0.5,
+            since: Some(2010),
+        }
+    );
+
+    // valueMap results have no endpoints
+    let value_map = r#"{"id":{"@type":"g:Int32","@value":7},"label":"knows","weight":{"@type":"g:Double","@value":0.5}}"#;
+    assert!(from_str::<Knows>(value_map).is_err());
+}
@@ -0,0 +1,129 @@
+use gremlin_rs::{
+    gremlin,
+    process::{bytecode::Bytecode, *},
+    structure::{
+        gson::{GsonMap, GsonV2},
+        Edge, Vertex,
+    },
+};
+use serde_json::Value;
+
+fn steps<S, E>(t: Traversal<S, E>) -> Value {
+    let b: Bytecode = t.into();
+    serde_json::to_value(b).unwrap()
+}
+
+#[test]
+fn expands_to_builder() {
+    let email = "marko@example.com";
+    let t: Traversal<Vertex, Vertex> = gremlin!("g.V().hasLabel('user').has('email', $email)");
+    assert_eq!(
+        steps(t),
+        steps(g.V(()).hasLabel("user").has(("email", email)))
+    );
+
+    let t: Traversal<Vertex, i64> = gremlin!("g.V().out('knows').count()");
+    assert_eq!(steps(t), steps(g.V(()).out("knows").count()));
+
+    let t = gremlin!(
+        "g.withSideEffect('x', 1).V().repeat(out()).times(2).limit(5L)
+            .order().by('age', desc).as('a').in('created').where(neq('a')).toList()"
+    );
+    assert_eq!(
+        steps(t),
+        steps(
+            g.withSideEffect(("x", 1))
+                .V(())
+                .repeat(__.out(()))
+                .times(2)
+                .limit(5i64)
+                .order()
+                .by(("age", Order::Desc))
+                .as_("a")
+                .in_("created")
+                .where_(P::neq("a"))
+        )
+    );
+
+    let (ids, max) = (vec![1i64, 2, 3], 1.0);
+    let expected = steps(
+        g.E(())
+            .has(("weight", P::gt(0.5f64).and(P::lt(max))))
+            .inV()
+            .has(("id", P::within(ids.clone())))
+            .has(("name", TextP::containing("ark")))
+            .toE(Direction::Out, "knows"),
+    );
+    let t: Traversal<Edge, Edge> = gremlin!(
+        "g.E().has('weight', gt(0.5d).and(lt($max))).inV().has('id', within($ids))
+            .has('name', TextP.containing('ark')).toE(OUT, 'knows')"
+    );
+    assert_eq!(steps(t), expected);
+}
+
+#[test]
+fn expands_arguments() {
+    let t = gremlin!(
+        "g.withStrategies(new SeedStrategy(seed: 7L), ReadOnlyStrategy)
+            .inject([1, 'a', null], [name: 'marko', age: 29], [:])
+            .property(VertexProperty.Cardinality.single, 'tags', ['a', 'b'])
+            .has(T.label, P.within('a', 'b'))"
+    );
+    let mut seed = TraversalStrategy::new("SeedStrategy");
+    seed.configuration
+        .insert("seed".to_string(), GsonV2::from(7i64));
+    let t2 = g
+        .withStrategies((seed, TraversalStrategy::new("ReadOnlyStrategy")))
+        .inject((
+            GsonV2::List(vec![GsonV2::from(1), GsonV2::from("a"), GsonV2::Null]),
+            GsonV2::Map(
+                [
+                    ("name".to_string(), GsonV2::from("marko")),
+                    ("age".to_string(), GsonV2::from(29)),
+                ]
+                .into(),
+            ),
+            GsonV2::Map(Default::default()),
+        ))
+        .property((
+            Cardinality::Single,
+            "tags",
+            GsonV2::List(vec![GsonV2::from("a"), GsonV2::from("b")]),
+        ))
+        .has((T::Label, P::within(("a", "b"))));
+    assert_eq!(steps(t), steps(t2));
+
+    let t = gremlin!("g.mergeV([(T.label): 'person', name: 'marko'])");
+    assert_eq!(
+        steps(t),
+        steps(
+            g.mergeV(
+                GsonMap::new()
+                    .with(T::Label, "person")
+                    .with("name", "marko")
+            )
+        )
+    );
+
+    let t = gremlin!(
+        "g.V().sack(Operator.addAll).barrier(normSack).select(Column.values)
+            .valueMap().with(WithOptions.tokens)"
+    );
+    assert_eq!(
+        steps(t),
+        steps(
+            g.V(())
+                .sack(Operator::AddAll)
+                .barrier(Barrier::NormSack)
+                .select(Column::Values)
+                .valueMap(())
+                .with(WithOptions::Tokens)
+        )
+    );
+
+    let t = gremlin!("__.as('a').out().as('b').select('a', 'b')");
+    assert_eq!(
+        steps(t),
+        steps(__.as_("a").out(()).as_("b").select(("a", "b")))
+    );
+}
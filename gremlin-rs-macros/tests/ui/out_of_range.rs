@@ -0,0 +1,5 @@
+use gremlin_rs::gremlin;
+
+fn main() {
+    let _ = gremlin!("g.V().repeat(out()).times(3000000000)");
+}
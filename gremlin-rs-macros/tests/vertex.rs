@@ -0,0 +1,113 @@
+use gremlin_rs::{
+    process::{bytecode::Bytecode, *},
+    structure::de::from_str,
+};
+use serde_json::Value;
+use std::collections::HashSet;
+
+#[derive(GremlinVertex, Debug, PartialEq)]
+#[gremlin(label = "person")]
+struct Person {
+    #[gremlin(id)]
+    id: i64,
+    name: String,
+    #[gremlin(name = "years")]
+    age: i32,
+    nick: Option<String>,
+    tags: Vec<String>,
+    roles: HashSet<String>,
+    #[gremlin(skip)]
+    cached: bool,
+}
+
+#[derive(GremlinVertex, Debug)]
+struct Software {
+    #[gremlin(id)]
+    id: Option<String>,
+    #[gremlin(cardinality = "single")]
+    lang: Vec<String>,
+}
+
+fn steps<S, E>(t: Traversal<S, E>) -> Value {
+    let b: Bytecode = t.into();
+    serde_json::to_value(b).unwrap()
+}
+
+fn marko() -> Person {
+    Person {
+        id: 1,
+        name: "marko".to_string(),
+        age: 29,
+        nick: None,
+        tags: vec!["a".to_string(), "b".to_string()],
+        roles: HashSet::from(["admin".to_string()]),
+        cached: true,
+    }
+}
+
+#[test]
+fn vertex_traversals() {
+    let p = marko();
+    assert_eq!(Person::LABEL, "person");
+    assert_eq!(
+        steps(p.add_vertex(&g)),
+        steps(
+            g.addV("person")
+                .property((T::Id, 1i64))
+                .property(("name", "marko"))
+                .property(("years", 29))
+                .property((Cardinality::List, "tags", "a"))
+                .property((Cardinality::List, "tags", "b"))
+                .property((Cardinality::Set, "roles", "admin"))
+        )
+    );
+    assert_eq!(
+        steps(p.update_vertex(&g)),
+        steps(
+            g.V(1i64)
+                .hasLabel("person")
+                .property((Cardinality::Single, "name", "marko"))
+                .property((Cardinality::Single, "years", 29))
+                .sideEffect(__.properties("nick").drop())
+                .sideEffect(__.properties("tags").drop())
+                .property((Cardinality::List, "tags", "a"))
+                .property((Cardinality::List, "tags", "b"))
+                .sideEffect(__.properties("roles").drop())
+                .property((Cardinality::Set, "roles", "admin"))
+        )
+    );
+
+    let s = Software {
+        id: None,
+        lang: vec!["java".to_string()],
+    };
+    assert_eq!(Software::LABEL, "Software");
+    assert!(s.vertex_id().is_none());
+    assert_eq!(
+        steps(s.add_vertex(&g)),
+        steps(g.addV("Software").property((
+            "lang",
+            gremlin_rs::structure::gson::GsonV2::List(vec!["java".into()])
+        )))
+    );
+    // without an id there is nothing to match, so the upsert adds
+    assert_eq!(steps(s.upsert_vertex(&g)), steps(s.add_vertex(&g)));
+}
+
+#[test]
+fn vertex_deserialization() {
+    let value_map = r#"{"id":{"@type":"g:Int64","@value":1},"label":"person","name":["marko"],"years":[{"@type":"g:Int32","@value":29}],"tags":["a","b"],"roles":["admin"]}"#;
+    let mut expected = marko();
+    expected.cached = false;
+    assert_eq!(from_str::<Person>(value_map).unwrap(), expected);
+
+    let element_map = r#"{"id":{"@type":"g:Int64","@value":1},"label":"person","name":"marko","years":{"@type":"g:Int32","@value":29},"nick":"mk","tags":"a"}"#;
+    let p = from_str::<Person>(element_map).unwrap();
+    assert_eq!(p.nick.as_deref(), Some("mk"));
+    assert_eq!(p.tags, ["a"]);
+    assert!(p.roles.is_empty());
+
+    let list = format!("[{}]", value_map);
+    assert_eq!(from_str::<Vec<Person>>(&list).unwrap().len(), 1);
+    assert!(from_str::<Person>(r#"{"id":{"@type":"g:Int64","@value":1}}"#).is_err());
+}
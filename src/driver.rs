@@ -1,6 +1,12 @@
+#[cfg(feature = "blocking")]
+pub mod blocking;
+mod cache;
 mod client;
+mod executor;
 mod pool;
 mod serialize;
 
+pub use cache::*;
 pub use client::*;
+pub use executor::*;
 pub use pool::*;
@@ -0,0 +1,135 @@
+use super::*;
+use crate::process::*;
+
+use serde::de::DeserializeOwned;
+use std::time::Duration;
+use tokio::runtime::{Builder, Runtime};
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+
+// ClientError holds tungstenite::Error unboxed, as the async Client returns it
+#[allow(clippy::result_large_err)]
+fn runtime() -> Result<Runtime, ClientError> {
+    // a worker thread keeps the client event loop running between blocking calls
+    Builder::new_multi_thread()
+        .worker_threads(1)
+        .enable_all()
+        .build()
+        .map_err(ClientError::RuntimeError)
+}
+
+pub struct BlockingClient {
+    client: Option<Client>,
+    rt: Runtime,
+}
+
+impl BlockingClient {
+    #[allow(clippy::result_large_err)]
+    pub fn new<T: IntoClientRequest + Unpin>(
+        url: T,
+        timeout_ms: u128,
+    ) -> Result<Self, ClientError> {
+        let rt = runtime()?;
+        let client = rt.block_on(Client::new(url, timeout_ms))?;
+        Ok(Self {
+            client: Some(client),
+            rt,
+        })
+    }
+
+    fn client(&self) -> &Client {
+        self.client.as_ref().unwrap()
+    }
+
+    #[allow(clippy::result_large_err)]
+    pub fn execute<S, E>(&self, query: Traversal<S, E>) -> Result<ClientResponse, ClientError> {
+        self.rt.block_on(self.client().execute(query))
+    }
+
+    #[allow(clippy::result_large_err)]
+    pub fn execute_parsed<T: DeserializeOwned, S, E>(
+        &self,
+        query: Traversal<S, E>,
+    ) -> Result<Vec<T>, ClientError> {
+        Ok(self.execute(query)?.parse()?)
+    }
+
+    #[allow(clippy::result_large_err)]
+    pub fn close(mut self, drain_timeout: Duration) -> Result<(), ClientError> {
+        let client = self.client.take().unwrap();
+        self.rt.block_on(client.close(drain_timeout))
+    }
+}
+
+impl Drop for BlockingClient {
+    fn drop(&mut self) {
+        // the main client must be dropped inside the runtime so its event loop receives Kill
+        let _guard = self.rt.enter();
+        self.client.take();
+    }
+}
+
+pub struct BlockingPool {
+    pool: Option<ClientPool>,
+    rt: Runtime,
+}
+
+impl BlockingPool {
+    #[allow(clippy::result_large_err)]
+    pub fn new<T: IntoClientRequest + Unpin + Clone>(
+        config: PoolConfig<T>,
+    ) -> Result<Self, ClientError> {
+        let rt = runtime()?;
+        let pool = rt.block_on(ClientPool::new(config))?;
+        Ok(Self {
+            pool: Some(pool),
+            rt,
+        })
+    }
+
+    fn pool(&self) -> &ClientPool {
+        self.pool.as_ref().unwrap()
+    }
+
+    #[allow(clippy::result_large_err)]
+    pub fn execute<S, E>(&self, query: Traversal<S, E>) -> Result<ClientResponse, ClientError> {
+        self.rt.block_on(self.pool().execute(query))
+    }
+
+    #[allow(clippy::result_large_err)]
+    pub fn execute_parsed<T: DeserializeOwned, S, E>(
+        &self,
+        query: Traversal<S, E>,
+    ) -> Result<Vec<T>, ClientError> {
+        Ok(self.execute(query)?.parse()?)
+    }
+
+    #[allow(clippy::result_large_err)]
+    pub fn close(mut self, drain_timeout: Duration) -> Result<(), ClientError> {
+        let pool = self.pool.take().unwrap();
+        self.rt.block_on(pool.close(drain_timeout))
+    }
+}
+
+impl Drop for BlockingPool {
+    fn drop(&mut self) {
+        let _guard = self.rt.enter();
+        self.pool.take();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{driver::client::tests::delayed_server, structure::gson::GsonV2};
+
+    #[test]
+    fn blocking_execute() {
+        let server_rt = Runtime::new().unwrap();
+        let url = server_rt.block_on(delayed_server(Duration::from_millis(10)));
+
+        let client = BlockingClient::new(url, 5000).unwrap();
+        let v: Vec<GsonV2> = client.execute_parsed(g.V(()).count()).unwrap();
+        assert!(v.is_empty());
+        client.close(Duration::from_secs(1)).unwrap();
+    }
+}
@@ -0,0 +1,372 @@
+use super::*;
+use crate::{
+    process::{bytecode::Bytecode, *},
+    structure::gson::GsonV2,
+};
+
+use serde_json::{to_string, to_value, Value};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet, VecDeque},
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+#[derive(Clone, Debug)]
+pub struct CacheConfig {
+    pub ttl_ms: u128,
+    pub max_entries: usize,
+    pub rules: HashMap<String, HashSet<String>>,
+}
+
+impl CacheConfig {
+    pub fn builder() -> Self {
+        CacheConfig {
+            ttl_ms: 5000,
+            max_entries: 1000,
+            rules: HashMap::new(),
+        }
+    }
+
+    pub fn ttl(&mut self, ms: u128) -> &mut Self {
+        self.ttl_ms = ms;
+        self
+    }
+
+    pub fn max_entries(&mut self, n: usize) -> &mut Self {
+        self.max_entries = n;
+        self
+    }
+
+    // writes touching `write_label` also invalidate cached reads touching `read_label`,
+    // a write always invalidates reads touching its own labels
+    pub fn invalidate_on(&mut self, write_label: &str, read_label: &str) -> &mut Self {
+        self.rules
+            .entry(write_label.to_string())
+            .or_default()
+            .insert(read_label.to_string());
+        self
+    }
+}
+
+pub struct CachedPool {
+    pool: ClientPool,
+    cache: Mutex<ResultCache>,
+}
+
+impl CachedPool {
+    pub fn new(pool: ClientPool, config: CacheConfig) -> Self {
+        Self {
+            pool,
+            cache: Mutex::new(ResultCache::new(config)),
+        }
+    }
+
+    pub fn pool(&self) -> &ClientPool {
+        &self.pool
+    }
+
+    pub async fn execute<S, E>(
+        &self,
+        query: Traversal<S, E>,
+    ) -> Result<ClientResponse, ClientError> {
+        if query.is_mutating() {
+            let labels = traversal_labels(&query);
+            let res = self.pool.execute(query).await;
+            self.cache.lock().unwrap().invalidate(&labels);
+            return res;
+        }
+
+        let key = cache_key(&query);
+        let labels = traversal_labels(&query);
+        let generation = {
+            let mut cache = self.cache.lock().unwrap();
+            if let Some(res) = cache.get(&key) {
+                return Ok(res);
+            }
+            cache.generation(&labels)
+        };
+
+        let res = self.pool.execute(query).await?;
+        // a write invalidating these labels while the read was in flight may have landed
+        // after the server answered it, so the response could already be stale
+        let mut cache = self.cache.lock().unwrap();
+        if cache.generation(&labels) == generation {
+            cache.insert(key, labels, res.clone());
+        }
+        Ok(res)
+    }
+
+    pub fn invalidate_all(&self) {
+        self.cache.lock().unwrap().clear();
+    }
+
+    pub async fn close(self, drain_timeout: Duration) -> Result<(), ClientError> {
+        self.pool.close(drain_timeout).await
+    }
+}
+
+struct CacheEntry {
+    inserted: Instant,
+    labels: HashSet<String>,
+    response: ClientResponse,
+}
+
+struct ResultCache {
+    config: CacheConfig,
+    entries: HashMap<String, CacheEntry>,
+    order: VecDeque<String>,
+    // invalidation counters per label, every write and every clear
+    generations: HashMap<String, u64>,
+    writes: u64,
+    clears: u64,
+}
+
+impl ResultCache {
+    fn new(config: CacheConfig) -> Self {
+        Self {
+            config,
+            entries: HashMap::new(),
+            order: VecDeque::new(),
+            generations: HashMap::new(),
+            writes: 0,
+            clears: 0,
+        }
+    }
+
+    // changes whenever a read touching `labels` would be invalidated, the counters only grow
+    // so their sum does too. reads without labels are invalidated by any write
+    fn generation(&self, labels: &HashSet<String>) -> u64 {
+        if labels.is_empty() {
+            return self.writes;
+        }
+        let invalidated: u64 = labels.iter().filter_map(|l| self.generations.get(l)).sum();
+        invalidated + self.clears
+    }
+
+    fn get(&mut self, key: &str) -> Option<ClientResponse> {
+        match self.entries.get(key) {
+            Some(e) if e.inserted.elapsed().as_millis() <= self.config.ttl_ms => {
+                Some(e.response.clone())
+            }
+            Some(_) => {
+                self.entries.remove(key);
+                self.order.retain(|k| k != key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    fn insert(&mut self, key: String, labels: HashSet<String>, response: ClientResponse) {
+        if self.config.max_entries == 0 {
+            return;
+        }
+        while self.entries.len() >= self.config.max_entries {
+            match self.order.pop_front() {
+                Some(k) => {
+                    self.entries.remove(&k);
+                }
+                None => break,
+            }
+        }
+        let entry = CacheEntry {
+            inserted: Instant::now(),
+            labels,
+            response,
+        };
+        if self.entries.insert(key.clone(), entry).is_none() {
+            self.order.push_back(key);
+        }
+    }
+
+    fn invalidate(&mut self, write_labels: &HashSet<String>) {
+        self.writes += 1;
+        // writes with no recognisable labels (e.g. g.V(id).drop()) may touch anything
+        if write_labels.is_empty() {
+            return self.clear();
+        }
+
+        let mut targets = write_labels.clone();
+        for l in write_labels.iter() {
+            if let Some(reads) = self.config.rules.get(l) {
+                targets.extend(reads.iter().cloned());
+            }
+        }
+        for l in targets.iter() {
+            *self.generations.entry(l.clone()).or_default() += 1;
+        }
+
+        self.entries
+            .retain(|_, e| !e.labels.is_empty() && e.labels.is_disjoint(&targets));
+        let entries = &self.entries;
+        self.order.retain(|k| entries.contains_key(k));
+    }
+
+    fn clear(&mut self) {
+        self.writes += 1;
+        self.clears += 1;
+        self.entries.clear();
+        self.order.clear();
+    }
+}
+
+fn cache_key<S, E>(query: &Traversal<S, E>) -> String {
+    let bytecode = to_value(query.bytecode()).map(sort_maps);
+    format!(
+        "{}:{}",
+        query.alias(),
+        bytecode.and_then(|b| to_string(&b)).unwrap_or_default()
+    )
+}
+
+// GsonV2::Map serializes in HashMap iteration order, which serde_json keeps when another crate
+// enables its preserve_order feature. equal traversals must share a key
+fn sort_maps(value: Value) -> Value {
+    match value {
+        Value::Object(map) => {
+            let sorted: BTreeMap<String, Value> =
+                map.into_iter().map(|(k, v)| (k, sort_maps(v))).collect();
+            Value::Object(sorted.into_iter().collect())
+        }
+        Value::Array(values) => Value::Array(values.into_iter().map(sort_maps).collect()),
+        value => value,
+    }
+}
+
+fn traversal_labels<S, E>(query: &Traversal<S, E>) -> HashSet<String> {
+    let mut labels = HashSet::new();
+    bytecode_labels(query.bytecode(), &mut labels);
+    labels
+}
+
+fn bytecode_labels(bytecode: &Bytecode, labels: &mut HashSet<String>) {
+    for step in bytecode.steps() {
+        if let Some((GsonV2::String(op), args)) = step.split_first() {
+            match (op.as_str(), args) {
+                ("has", [GsonV2::String(l), _, _]) => {
+                    labels.insert(l.clone());
+                }
+                (
+                    "hasLabel" | "addV" | "addE" | "out" | "in" | "both" | "outE" | "inE" | "bothE"
+                    | "toE",
+                    _,
+                ) => {
+                    for a in args.iter() {
+                        if let GsonV2::String(l) = a {
+                            labels.insert(l.clone());
+                        }
+                    }
+                }
+                _ => {}
+            }
+            for a in args.iter() {
+                if let GsonV2::Bytecode(b) = a {
+                    bytecode_labels(b, labels);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn labels<S, E>(query: Traversal<S, E>) -> HashSet<String> {
+        traversal_labels(&query)
+    }
+
+    fn insert<S, E>(cache: &mut ResultCache, query: &Traversal<S, E>) {
+        cache.insert(
+            cache_key(query),
+            traversal_labels(query),
+            ClientResponse(vec![]),
+        );
+    }
+
+    #[test]
+    fn label_invalidation() {
+        let mut config = CacheConfig::builder();
+        config.invalidate_on("knows", "person");
+        let mut cache = ResultCache::new(config);
+
+        let person = g.V(()).hasLabel("person").count();
+        let software = g.V(()).hasLabel("software").count();
+        insert(&mut cache, &person);
+        insert(&mut cache, &software);
+
+        cache.invalidate(&labels(g.V("1").addE("knows").to(__.V("2"))));
+        assert!(cache.get(&cache_key(&person)).is_none());
+        assert!(cache.get(&cache_key(&software)).is_some());
+
+        cache.invalidate(&labels(g.V("1").drop()));
+        assert!(cache.get(&cache_key(&software)).is_none());
+    }
+
+    #[test]
+    fn generations() {
+        let mut config = CacheConfig::builder();
+        config.invalidate_on("knows", "person");
+        let mut cache = ResultCache::new(config);
+
+        let person = labels(g.V(()).hasLabel("person").count());
+        let software = labels(g.V(()).hasLabel("software").count());
+        let (p, s, none) = (
+            cache.generation(&person),
+            cache.generation(&software),
+            cache.generation(&HashSet::new()),
+        );
+        cache.invalidate(&labels(g.V("1").addE("knows").to(__.V("2"))));
+        assert_ne!(cache.generation(&person), p);
+        assert_eq!(cache.generation(&software), s);
+        assert_ne!(cache.generation(&HashSet::new()), none);
+
+        let s = cache.generation(&software);
+        cache.invalidate(&labels(g.V("1").drop()));
+        assert_ne!(cache.generation(&software), s);
+    }
+
+    #[test]
+    fn map_keys_sorted() {
+        let entries: Vec<(String, GsonV2)> =
+            (0..32).map(|i| (i.to_string(), GsonV2::from(i))).collect();
+        let forward: HashMap<String, GsonV2> = entries.iter().cloned().collect();
+        let reverse: HashMap<String, GsonV2> = entries.into_iter().rev().collect();
+        assert_eq!(
+            cache_key(&g.inject(GsonV2::Map(forward))),
+            cache_key(&g.inject(GsonV2::Map(reverse)))
+        );
+    }
+
+    #[test]
+    fn size_limit() {
+        let mut config = CacheConfig::builder();
+        config.max_entries(1);
+        let mut cache = ResultCache::new(config);
+
+        cache.insert("a".to_string(), HashSet::new(), ClientResponse(vec![]));
+        cache.insert("b".to_string(), HashSet::new(), ClientResponse(vec![]));
+        assert!(cache.get("a").is_none());
+        assert!(cache.get("b").is_some());
+    }
+
+    #[test]
+    fn ttl_expiry() {
+        let mut config = CacheConfig::builder();
+        config.ttl(0).max_entries(2);
+        let mut cache = ResultCache::new(config);
+
+        cache.insert("a".to_string(), HashSet::new(), ClientResponse(vec![]));
+        std::thread::sleep(Duration::from_millis(2));
+        assert!(cache.get("a").is_none());
+        assert!(cache.order.is_empty());
+
+        // a re-inserted key is ordered once, so it can't evict a live entry twice
+        cache.insert("a".to_string(), HashSet::new(), ClientResponse(vec![]));
+        cache.insert("b".to_string(), HashSet::new(), ClientResponse(vec![]));
+        assert_eq!(cache.order, ["a", "b"]);
+        cache.insert("c".to_string(), HashSet::new(), ClientResponse(vec![]));
+        assert_eq!(cache.order, ["b", "c"]);
+        assert_eq!(cache.entries.len(), 2);
+    }
+}
@@ -1,4 +1,7 @@
-use futures::{stream::StreamExt, SinkExt};
+use futures::{
+    stream::{BoxStream, StreamExt},
+    SinkExt,
+};
 use serde_json::to_vec;
 use thiserror::Error;
 use tokio::{
@@ -22,13 +25,59 @@ use std::{
 #[cfg(test)]
 use serde_json::to_string_pretty;
 
-use crate::process::{bytecode::Bytecode, Traversal};
+use crate::{
+    process::{bytecode::Bytecode, validator::ValidationError, Traversal},
+    structure::de::GsonError,
+};
 
 use super::serialize::*;
 
 type OneshotItem = Result<Vec<Vec<u8>>, ClientError>;
-type MpscItem = (Bytecode, oneshot::Sender<OneshotItem>);
-type PendingItem = (Vec<Vec<u8>>, oneshot::Sender<OneshotItem>);
+type FrameItem = Result<Vec<u8>, ClientError>;
+type MpscItem = (Bytecode, String, Responder);
+
+// where a request's frames go: collected until the last one for execute, or passed on as they
+// arrive for execute_stream
+#[derive(Debug)]
+pub(crate) enum Responder {
+    Collect(Vec<Vec<u8>>, oneshot::Sender<OneshotItem>),
+    Stream(mpsc::UnboundedSender<FrameItem>),
+}
+
+impl Responder {
+    // a 206 partial content frame
+    fn partial(&mut self, frame: Vec<u8>) {
+        match self {
+            Responder::Collect(data, _) => data.push(frame),
+            Responder::Stream(tx) => {
+                let _ = tx.send(Ok(frame));
+            }
+        }
+    }
+
+    fn complete(self, frame: Vec<u8>) {
+        match self {
+            Responder::Collect(mut data, tx) => {
+                data.push(frame);
+                let _ = tx.send(Ok(data));
+            }
+            Responder::Stream(tx) => {
+                let _ = tx.send(Ok(frame));
+            }
+        }
+    }
+
+    fn fail(self, e: ClientError) {
+        match self {
+            Responder::Collect(_, tx) => {
+                let _ = tx.send(Err(e));
+            }
+            Responder::Stream(tx) => {
+                let _ = tx.send(Err(e));
+            }
+        }
+    }
+}
 
 #[derive(Error, Debug)]
 pub enum ClientError {
@@ -46,9 +95,21 @@ pub enum ClientError {
     ClientClosed,
     #[error("no available clients")]
     NoClients,
+    #[error("traversal source has no remote, pass a client or use TraversalSource::with_remote")]
+    NoRemote,
+    #[error("invalid traversal: {0}")]
+    InvalidTraversal(#[from] ValidationError),
+    #[error("error parsing gremlin response: {0}")]
+    ParseError(#[from] GsonError),
+    #[error("traversal returned no results, expected exactly one")]
+    NoResult,
+    #[error("traversal returned more than one result, expected exactly one")]
+    MultipleResults,
+    #[error("error creating client runtime: {0}")]
+    RuntimeError(std::io::Error),
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ClientResponse(pub Vec<Vec<u8>>);
 
 impl From<Vec<Vec<u8>>> for ClientResponse {
@@ -74,6 +135,7 @@ impl Into<Message> for GremlinRequest {
 pub(crate) enum EventType {
     Ws(Vec<u8>),
     Rx(MpscItem),
+    Close(time::Duration, oneshot::Sender<()>),
     Kill,
     Timeouts,
 }
@@ -116,8 +178,9 @@ impl Client {
         });
 
         spawn(async move {
-            let mut pending: HashMap<Uuid, PendingItem> = HashMap::new();
+            let mut pending: HashMap<Uuid, Responder> = HashMap::new();
             let mut timeouts: VecDeque<(u128, Uuid)> = VecDeque::new();
+            let mut closing: Option<(u128, Vec<oneshot::Sender<()>>)> = None;
             while let Some(val) = rx_stream.next().await {
                 match val {
                     Ws(res) => {
@@ -130,8 +193,8 @@ impl Client {
                                 e,
                                 from_utf8(&res).unwrap_or("invalid_utf8")
                             );
-                            for (_, (_, sender)) in pending.drain() {
-                                sender.send(Err(ClientError::NoClients)).unwrap();
+                            for (_, responder) in pending.drain() {
+                                responder.fail(ClientError::NoClients);
                             }
                         }
 
@@ -143,93 +206,99 @@ impl Client {
                                     to_string_pretty(&h).unwrap(),
                                     from_utf8(&res).unwrap_or("invalid_utf8")
                                 );
-                                for (_, (_, sender)) in pending.drain() {
-                                    sender.send(Err(ClientError::NoClients)).unwrap();
+                                for (_, responder) in pending.drain() {
+                                    responder.fail(ClientError::NoClients);
                                 }
                             }
 
                             if let Some(request_id) = h.request_id {
                                 match h.status.code {
                                     200 | 204 => {
-                                        if let Some((mut data, os_sender)) =
-                                            pending.remove(&request_id)
-                                        {
-                                            data.push(res);
-                                            match os_sender.send(Ok(data)) {
-                                                _ => {}
-                                            }
+                                        if let Some(responder) = pending.remove(&request_id) {
+                                            responder.complete(res);
                                         }
                                     }
                                     206 => {
-                                        if let Some(p) = pending.get_mut(&request_id) {
-                                            p.0.push(res);
+                                        if let Some(responder) = pending.get_mut(&request_id) {
+                                            responder.partial(res);
                                         }
                                     }
                                     x => {
-                                        if let Some((_, os_sender)) = pending.remove(&request_id) {
-                                            match os_sender.send(Err(ClientError::ResponseError(
+                                        if let Some(responder) = pending.remove(&request_id) {
+                                            responder.fail(ClientError::ResponseError(
                                                 x as usize,
                                                 from_utf8(&res)
                                                     .unwrap_or("invalid utf8")
                                                     .to_string(),
-                                            ))) {
-                                                _ => {}
-                                            }
+                                            ));
                                         }
                                     }
                                 }
                             }
                         }
                     }
-                    Rx((bytecode, os_sender)) => {
-                        let (request_id, request) = GremlinRequest::new(bytecode);
+                    Rx((_, _, responder)) if closing.is_some() => {
+                        responder.fail(ClientError::ClientClosed);
+                    }
+                    Rx((bytecode, alias, responder)) => {
+                        let (request_id, request) = GremlinRequest::new(bytecode, alias);
                         match sink.send(request.into()).await {
                             Ok(_) => {
-                                pending.insert(request_id.clone(), (Vec::new(), os_sender));
-                                timeouts.push_back((
-                                    time::SystemTime::now()
-                                        .duration_since(time::UNIX_EPOCH)
-                                        .unwrap()
-                                        .as_millis()
-                                        + timeout_ms,
-                                    request_id,
-                                ))
+                                pending.insert(request_id.clone(), responder);
+                                timeouts.push_back((now_ms() + timeout_ms, request_id))
                             }
-                            Err(e) => os_sender.send(Err(ClientError::NetworkError(e))).unwrap(),
+                            Err(e) => responder.fail(ClientError::NetworkError(e)),
                         };
                     }
+                    Close(drain_timeout, done) => {
+                        let deadline = now_ms() + drain_timeout.as_millis();
+                        match closing.as_mut() {
+                            Some((d, waiters)) => {
+                                *d = deadline.min(*d);
+                                waiters.push(done);
+                            }
+                            None => closing = Some((deadline, vec![done])),
+                        }
+                    }
                     Kill => {
                         rx_stream.close();
-                        sink.send(Message::Close(None)).await.unwrap();
-                        for (_, (_, sender)) in pending.drain() {
-                            match sender.send(Err(ClientError::ClientClosed)) {
-                                _ => (),
-                            };
+                        let _ = sink.send(Message::Close(None)).await;
+                        for (_, responder) in pending.drain() {
+                            responder.fail(ClientError::ClientClosed);
                         }
                         break;
                     }
                     Timeouts => {
-                        let now = time::SystemTime::now()
-                            .duration_since(time::UNIX_EPOCH)
-                            .unwrap()
-                            .as_millis();
-
-                        loop {
-                            if let Some((t, request_id)) = timeouts.pop_front() {
-                                if t < now {
-                                    if let Some((_, os_sender)) = pending.remove(&request_id) {
-                                        match os_sender.send(Err(ClientError::RequestTimeout)) {
-                                            _ => {}
-                                        }
-                                    }
-                                } else {
-                                    timeouts.push_front((t, request_id));
-                                    break;
+                        let now = now_ms();
+
+                        while let Some((t, request_id)) = timeouts.pop_front() {
+                            if t < now {
+                                if let Some(responder) = pending.remove(&request_id) {
+                                    responder.fail(ClientError::RequestTimeout);
                                 }
+                            } else {
+                                timeouts.push_front((t, request_id));
+                                break;
                             }
                         }
                     }
                 }
+
+                if let Some((deadline, _)) = &closing {
+                    if pending.is_empty() || *deadline < now_ms() {
+                        rx_stream.close();
+                        let _ = sink.send(Message::Close(None)).await;
+                        for (_, responder) in pending.drain() {
+                            responder.fail(ClientError::ClientClosed);
+                        }
+                        if let Some((_, waiters)) = closing.take() {
+                            for done in waiters {
+                                let _ = done.send(());
+                            }
+                        }
+                        break;
+                    }
+                }
             }
         });
 
@@ -239,12 +308,23 @@ impl Client {
         Ok(Client { tx, main: true })
     }
 
-    pub async fn execute(&self, query: Traversal) -> Result<ClientResponse, ClientError> {
+    pub async fn execute<S, E>(
+        &self,
+        query: Traversal<S, E>,
+    ) -> Result<ClientResponse, ClientError> {
+        // debug builds reject traversals the server would fail on before sending them
+        #[cfg(debug_assertions)]
+        query.validate()?;
+
+        let alias = query.alias().to_string();
         let bytecode: Bytecode = query.into();
 
         let (os_tx, os_rx) = oneshot::channel();
 
-        if let Err(_) = self.tx.send(Rx((bytecode, os_tx))) {
+        if let Err(_) = self
+            .tx
+            .send(Rx((bytecode, alias, Responder::Collect(Vec::new(), os_tx))))
+        {
             return Err(ClientError::ExecutionError);
         }
         os_rx
@@ -252,6 +332,47 @@ impl Client {
             .map_err(|_| ClientError::ExecutionError)?
             .map(|v| v.into())
     }
+
+    // yields each response frame as it arrives, so 206 partial responses can be read before
+    // the server has sent the last frame
+    pub fn execute_stream<S, E>(&self, query: Traversal<S, E>) -> BoxStream<'static, FrameItem> {
+        let (frames_tx, frames_rx) = mpsc::unbounded_channel();
+        let frames = UnboundedReceiverStream::new(frames_rx).boxed();
+
+        #[cfg(debug_assertions)]
+        if let Err(e) = query.validate() {
+            let _ = frames_tx.send(Err(e.into()));
+            return frames;
+        }
+
+        let alias = query.alias().to_string();
+        let bytecode: Bytecode = query.into();
+        if let Err(mpsc::error::SendError(Rx((_, _, responder)))) =
+            self.tx
+                .send(Rx((bytecode, alias, Responder::Stream(frames_tx))))
+        {
+            responder.fail(ClientError::ExecutionError);
+        }
+        frames
+    }
+
+    pub async fn close(mut self, drain_timeout: time::Duration) -> Result<(), ClientError> {
+        // the event loop is shut down explicitly, so dropping this handle must not send Kill
+        self.main = false;
+
+        let (done_tx, done_rx) = oneshot::channel();
+        if self.tx.send(Close(drain_timeout, done_tx)).is_err() {
+            return Err(ClientError::ClientClosed);
+        }
+        done_rx.await.map_err(|_| ClientError::ClientClosed)
+    }
+}
+
+fn now_ms() -> u128 {
+    time::SystemTime::now()
+        .duration_since(time::UNIX_EPOCH)
+        .unwrap()
+        .as_millis()
 }
 
 impl Clone for Client {
@@ -266,7 +387,214 @@ impl Clone for Client {
 impl Drop for Client {
     fn drop(&mut self) {
         if self.main {
-            self.tx.send(Kill).unwrap()
+            let _ = self.tx.send(Kill);
         }
     }
 }
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::{process::*, structure::gson::GsonMap};
+    use std::sync::Arc;
+    use tokio::{
+        net::TcpListener,
+        time::{sleep, timeout},
+    };
+    use tokio_tungstenite::accept_async;
+
+    pub(crate) async fn delayed_server(delay: time::Duration) -> String {
+        mock_server(delay, "null").await
+    }
+
+    fn request_id(bin: &[u8]) -> String {
+        let req: serde_json::Value = serde_json::from_slice(&bin[34..]).unwrap();
+        req["requestId"]["@value"].as_str().unwrap().to_string()
+    }
+
+    fn response(request_id: &str, code: u16, data: &str) -> Message {
+        let res = format!(
+            r#"{{"requestId":"{}","status":{{"message":"","code":{},"attributes":{{}}}},"result":{{"data":{},"meta":{{}}}}}}"#,
+            request_id, code, data
+        );
+        Message::Binary(res.into_bytes())
+    }
+
+    // replies to every request with the given result data after `delay`
+    pub(crate) async fn mock_server(delay: time::Duration, data: &'static str) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        spawn(async move {
+            let (tcp, _) = listener.accept().await.unwrap();
+            let mut ws = accept_async(tcp).await.unwrap();
+            while let Some(Ok(Message::Binary(bin))) = ws.next().await {
+                let request_id = request_id(&bin);
+                sleep(delay).await;
+                let code = if data == "null" { 204 } else { 200 };
+                if ws.send(response(&request_id, code, data)).await.is_err() {
+                    break;
+                }
+            }
+        });
+        format!("ws://{}", addr)
+    }
+
+    #[tokio::test]
+    async fn stream_yields_partial_frames() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        let (release_tx, release_rx) = oneshot::channel::<()>();
+        spawn(async move {
+            let (tcp, _) = listener.accept().await.unwrap();
+            let mut ws = accept_async(tcp).await.unwrap();
+            if let Some(Ok(Message::Binary(bin))) = ws.next().await {
+                let request_id = request_id(&bin);
+                ws.send(response(&request_id, 206, "[1]")).await.unwrap();
+                // the last frame is held back until the client has read the first
+                release_rx.await.unwrap();
+                ws.send(response(&request_id, 200, "[2]")).await.unwrap();
+            }
+        });
+        let client = Client::new(url, 5000).await.unwrap();
+
+        let mut frames = client.execute_stream(g.V(()).values("age"));
+        let first = timeout(time::Duration::from_secs(2), frames.next())
+            .await
+            .expect("the partial frame arrives before the last one");
+        assert!(from_utf8(&first.unwrap().unwrap())
+            .unwrap()
+            .contains(r#""code":206"#));
+        release_tx.send(()).unwrap();
+        let last = frames.next().await.unwrap().unwrap();
+        assert!(from_utf8(&last).unwrap().contains(r#""code":200"#));
+        assert!(frames.next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn merge_map_wire_format() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        let (sent_tx, sent_rx) = oneshot::channel::<Vec<u8>>();
+        spawn(async move {
+            let (tcp, _) = listener.accept().await.unwrap();
+            let mut ws = accept_async(tcp).await.unwrap();
+            if let Some(Ok(Message::Binary(bin))) = ws.next().await {
+                ws.send(response(&request_id(&bin), 204, "null"))
+                    .await
+                    .unwrap();
+                sent_tx.send(bin).unwrap();
+            }
+        });
+        let client = Client::new(url, 5000).await.unwrap();
+
+        let search = GsonMap::new()
+            .with(T::Label, "person")
+            .with("name", "marko");
+        client.execute(g.mergeV(search)).await.unwrap();
+
+        let sent = sent_rx.await.unwrap();
+        // the g:Map goes out under the v2 mimetype, see GsonMap
+        assert_eq!(&sent[..34], b"!application/vnd.gremlin-v2.0+json");
+        let req: serde_json::Value = serde_json::from_slice(&sent[34..]).unwrap();
+        assert_eq!(
+            req["args"]["gremlin"]["@value"]["step"][0],
+            serde_json::json!(["mergeV", {"@type": "g:Map", "@value": [
+                {"@type": "g:T", "@value": "label"}, "person", "name", "marko"
+            ]}])
+        );
+    }
+
+    #[tokio::test]
+    async fn close_drains_pending_requests() {
+        let url = delayed_server(time::Duration::from_millis(200)).await;
+        let client = Client::new(url, 5000).await.unwrap();
+
+        let c = client.clone();
+        let in_flight = spawn(async move { c.execute(g.V(()).count()).await });
+        sleep(time::Duration::from_millis(50)).await;
+
+        client.close(time::Duration::from_secs(5)).await.unwrap();
+        assert!(in_flight.await.unwrap().is_ok());
+    }
+
+    #[tokio::test]
+    async fn close_rejects_after_deadline() {
+        let url = delayed_server(time::Duration::from_secs(5)).await;
+        let client = Client::new(url, 10000).await.unwrap();
+
+        let c = client.clone();
+        let in_flight = spawn(async move { c.execute(g.V(()).count()).await });
+        sleep(time::Duration::from_millis(50)).await;
+
+        client
+            .close(time::Duration::from_millis(100))
+            .await
+            .unwrap();
+        assert!(matches!(
+            in_flight.await.unwrap(),
+            Err(ClientError::ClientClosed)
+        ));
+    }
+
+    #[tokio::test]
+    async fn terminal_steps() {
+        let data = r#"[{"@type":"g:Int64","@value":3},{"@type":"g:Int64","@value":4}]"#;
+        let url = mock_server(time::Duration::ZERO, data).await;
+        let client = Client::new(url, 5000).await.unwrap();
+
+        assert_eq!(g.V(()).count().next(&client).await.unwrap(), Some(3));
+        assert_eq!(g.V(()).count().next_n(2, &client).await.unwrap(), [3, 4]);
+        assert!(g.V(()).count().has_next(&client).await.unwrap());
+        assert_eq!(g.V(()).count().to_set(&client).await.unwrap().len(), 2);
+        assert!(g.V(()).drop().iterate(&client).await.is_ok());
+        assert!(matches!(
+            g.V(()).count().one(&client).await,
+            Err(ClientError::MultipleResults)
+        ));
+
+        let url = delayed_server(time::Duration::ZERO).await;
+        let client = Client::new(url, 5000).await.unwrap();
+
+        assert!(!g.V(()).has_next(&client).await.unwrap());
+        assert!(matches!(
+            g.V(()).count().one(&client).await,
+            Err(ClientError::NoResult)
+        ));
+    }
+
+    #[tokio::test]
+    async fn validates_before_sending() {
+        let url = delayed_server(time::Duration::ZERO).await;
+        let client = Client::new(url, 5000).await.unwrap();
+
+        assert!(matches!(
+            client.execute(g.V(()).out(()).times(2)).await,
+            Err(ClientError::InvalidTraversal(_))
+        ));
+        assert!(client
+            .execute(g.V(()).repeat(__.out(())).times(2))
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn bound_remote() {
+        let data = r#"[{"@type":"g:Int64","@value":3}]"#;
+        let url = mock_server(time::Duration::ZERO, data).await;
+        let client = Client::new(url, 5000).await.unwrap();
+
+        let bound = g.with_remote(client.clone());
+        assert_eq!(bound.V(()).count().next(()).await.unwrap(), Some(3));
+        assert_eq!(bound.V(()).count().one(&client).await.unwrap(), 3);
+
+        let remote: Arc<dyn crate::driver::GremlinExecutor> = Arc::new(client.clone());
+        let bound = TraversalSource::with_alias("gmodern").with_remote(remote);
+        assert!(bound.V(()).count().has_next(()).await.unwrap());
+
+        assert!(!g.is_bound());
+        assert!(matches!(
+            g.V(()).count().next(()).await,
+            Err(ClientError::NoRemote)
+        ));
+    }
+}
@@ -0,0 +1,117 @@
+use super::*;
+use crate::process::Traversal;
+
+use futures::{
+    future::BoxFuture,
+    stream::{self, BoxStream, StreamExt},
+};
+use std::sync::Arc;
+
+// object safe so a TraversalSource can hold any executor behind an Arc, implement it to put
+// middleware or a test double in front of (or instead of) a Client
+pub trait GremlinExecutor: Send + Sync {
+    fn execute(&self, query: Traversal) -> BoxFuture<'_, Result<ClientResponse, ClientError>>;
+
+    // yields each response frame, Client and ClientPool as the frames arrive. the default, for
+    // executors without a connection of their own, waits for the full response
+    fn execute_stream(&self, query: Traversal) -> BoxStream<'_, Result<Vec<u8>, ClientError>> {
+        stream::once(self.execute(query))
+            .flat_map(|res| match res {
+                Ok(r) => stream::iter(r.0.into_iter().map(Ok)).boxed(),
+                Err(e) => stream::once(async { Err(e) }).boxed(),
+            })
+            .boxed()
+    }
+}
+
+impl GremlinExecutor for Client {
+    fn execute(&self, query: Traversal) -> BoxFuture<'_, Result<ClientResponse, ClientError>> {
+        Box::pin(Client::execute(self, query))
+    }
+
+    fn execute_stream(&self, query: Traversal) -> BoxStream<'_, Result<Vec<u8>, ClientError>> {
+        Client::execute_stream(self, query)
+    }
+}
+
+impl GremlinExecutor for ClientPool {
+    fn execute(&self, query: Traversal) -> BoxFuture<'_, Result<ClientResponse, ClientError>> {
+        Box::pin(ClientPool::execute(self, query))
+    }
+
+    fn execute_stream(&self, query: Traversal) -> BoxStream<'_, Result<Vec<u8>, ClientError>> {
+        ClientPool::execute_stream(self, query)
+    }
+}
+
+impl GremlinExecutor for CachedPool {
+    fn execute(&self, query: Traversal) -> BoxFuture<'_, Result<ClientResponse, ClientError>> {
+        Box::pin(CachedPool::execute(self, query))
+    }
+}
+
+impl<X: GremlinExecutor + ?Sized> GremlinExecutor for Arc<X> {
+    fn execute(&self, query: Traversal) -> BoxFuture<'_, Result<ClientResponse, ClientError>> {
+        (**self).execute(query)
+    }
+
+    fn execute_stream(&self, query: Traversal) -> BoxStream<'_, Result<Vec<u8>, ClientError>> {
+        (**self).execute_stream(query)
+    }
+}
+
+impl<X: GremlinExecutor + ?Sized> GremlinExecutor for Box<X> {
+    fn execute(&self, query: Traversal) -> BoxFuture<'_, Result<ClientResponse, ClientError>> {
+        (**self).execute(query)
+    }
+
+    fn execute_stream(&self, query: Traversal) -> BoxStream<'_, Result<Vec<u8>, ClientError>> {
+        (**self).execute_stream(query)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::process::{bytecode::Bytecode, *};
+    use std::sync::Mutex;
+
+    // records each query and replies with a fixed frame
+    struct Recorder {
+        queries: Mutex<Vec<Bytecode>>,
+        frame: &'static str,
+    }
+
+    impl GremlinExecutor for Recorder {
+        fn execute(&self, query: Traversal) -> BoxFuture<'_, Result<ClientResponse, ClientError>> {
+            self.queries.lock().unwrap().push(query.into());
+            let res = ClientResponse(vec![self.frame.as_bytes().to_vec()]);
+            Box::pin(async { Ok(res) })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_double() {
+        let recorder = Recorder {
+            queries: Mutex::new(vec![]),
+            frame: r#"{"requestId":"b65e6f64-a839-4c3f-a33b-047d9798f94a","status":{"message":"","code":200,"attributes":{}},"result":{"data":[{"@type":"g:Int64","@value":7}],"meta":{}}}"#,
+        };
+
+        let count = g.V(()).count().next(&recorder).await.unwrap();
+        assert_eq!(count, Some(7));
+
+        let dyn_recorder: &dyn GremlinExecutor = &recorder;
+        let frames: Vec<_> = dyn_recorder
+            .execute_stream(g.V(()).untyped())
+            .collect()
+            .await;
+        assert_eq!(frames.len(), 1);
+
+        let queries = recorder.queries.lock().unwrap();
+        assert_eq!(queries.len(), 2);
+        assert_eq!(
+            serde_json::to_value(queries[0].steps().last().unwrap()).unwrap(),
+            serde_json::json!(["limit", {"@type": "g:Int64", "@value": 1}])
+        );
+    }
+}
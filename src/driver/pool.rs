@@ -1,7 +1,12 @@
 use super::*;
 use crate::process::*;
 
+use futures::{
+    future::join_all,
+    stream::{self, BoxStream, StreamExt},
+};
 use rand::{prelude::*, seq::SliceRandom};
+use std::time::Duration;
 use tokio_tungstenite::tungstenite::client::IntoClientRequest;
 
 pub struct ClientPool {
@@ -30,20 +35,43 @@ impl ClientPool {
         Ok(Self { readers, writers })
     }
 
-    pub async fn execute(&self, query: Traversal) -> Result<ClientResponse, ClientError> {
+    // ThreadRng is not Send so it must not be held across an await
+    fn client<S, E>(&self, query: &Traversal<S, E>) -> Option<&Client> {
         let mut rng = thread_rng();
-        let client = if query.is_mutating() {
-            self.writers
-                .choose(&mut rng)
-                .ok_or(ClientError::NoClients)?
+        if query.is_mutating() {
+            self.writers.choose(&mut rng)
         } else {
-            self.readers
-                .choose(&mut rng)
-                .ok_or(ClientError::NoClients)?
-        };
+            self.readers.choose(&mut rng)
+        }
+    }
 
+    pub async fn execute<S, E>(
+        &self,
+        query: Traversal<S, E>,
+    ) -> Result<ClientResponse, ClientError> {
+        let client = self.client(&query).ok_or(ClientError::NoClients)?;
         client.execute(query).await
     }
+
+    pub fn execute_stream<S, E>(
+        &self,
+        query: Traversal<S, E>,
+    ) -> BoxStream<'static, Result<Vec<u8>, ClientError>> {
+        match self.client(&query) {
+            Some(client) => client.execute_stream(query),
+            None => stream::once(async { Err(ClientError::NoClients) }).boxed(),
+        }
+    }
+
+    pub async fn close(self, drain_timeout: Duration) -> Result<(), ClientError> {
+        let closing = self
+            .writers
+            .into_iter()
+            .chain(self.readers)
+            .map(|c| c.close(drain_timeout));
+
+        join_all(closing).await.into_iter().collect()
+    }
 }
 
 pub struct PoolConfig<T> {
@@ -68,7 +68,7 @@ pub(crate) struct GremlinRequest {
 }
 
 impl GremlinRequest {
-    pub fn new(b: bytecode::Bytecode) -> (Uuid, Self) {
+    pub fn new(b: bytecode::Bytecode, alias: String) -> (Uuid, Self) {
         let u = Uuid::new_v4();
         (
             u.clone(),
@@ -78,7 +78,7 @@ impl GremlinRequest {
                 processor: "traversal",
                 args: RequestArgs {
                     gremlin: ReqEnum::Bytecode(b),
-                    aliases: RequestAliases { g: "g" },
+                    aliases: RequestAliases { g: alias },
                 },
             },
         )
@@ -93,7 +93,7 @@ pub(crate) struct RequestArgs {
 
 #[derive(Serialize, Debug)]
 pub(crate) struct RequestAliases {
-    pub(crate) g: &'static str,
+    pub(crate) g: String,
 }
 
 #[cfg(test)]
@@ -105,10 +105,19 @@ mod tests {
     #[test]
     fn request_serialization() {
         let bytecode = bytecode::Bytecode::new();
-        let (_, req) = GremlinRequest::new(bytecode);
+        let (_, req) = GremlinRequest::new(bytecode, "g".to_string());
         println!("{}", to_string_pretty(&req).unwrap());
     }
 
+    #[test]
+    fn request_alias_serialization() {
+        let traversal = crate::process::TraversalSource::with_alias("gmodern").V(());
+        let alias = traversal.alias().to_string();
+        let (_, req) = GremlinRequest::new(traversal.into(), alias);
+        let v = serde_json::to_value(&req).unwrap();
+        assert_eq!(v["args"]["aliases"]["g"], "gmodern");
+    }
+
     #[test]
     fn response_deserialization() {
         let null_data = r#"{"requestId":"b65e6f64-a839-4c3f-a33b-047d9798f94a","status":{"message":"","code":204,"attributes":{"host":"/172.31.14.18:55854"}},"result":{"data":null,"meta":{}}}"#;
@@ -150,5 +159,10 @@ mod tests {
             "vtx data parsed:\n{}",
             to_string_pretty(&vtx_parsed).unwrap()
         );
+
+        let typed_parsed = from_str::<GremlinResponse<crate::structure::Vertex>>(vtx_data).unwrap();
+        let vertices = typed_parsed.result.data.unwrap();
+        assert_eq!(vertices.len(), 2);
+        assert_eq!(vertices[1].label, "user");
     }
 }
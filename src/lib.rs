@@ -5,6 +5,15 @@ pub mod driver;
 pub mod process;
 pub mod structure;
 
+#[cfg(feature = "macros")]
+pub use gremlin_rs_macros::gremlin;
+
+// used by code the derive macros generate, so callers need no serde dependency of their own
+#[doc(hidden)]
+pub mod __private {
+    pub use serde;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -24,13 +33,10 @@ mod tests {
             println!("created remote client");
             println!("testing query execution...");
 
-            let result = timeout(
-                Duration::from_secs(5),
-                g.V(()).sample((1,)).to_list(&client),
-            )
-            .await
-            .unwrap()
-            .unwrap();
+            let result = timeout(Duration::from_secs(5), client.execute(g.V(()).sample(1)))
+                .await
+                .unwrap()
+                .unwrap();
 
             println!("{:?}", &result);
 
@@ -42,7 +48,7 @@ mod tests {
 
             let result = timeout(
                 Duration::from_secs(5),
-                g.V(()).sample(25).group(()).by(__.label()).to_list(&client),
+                client.execute(g.V(()).sample(25).group(()).by(__.label())),
             )
             .await
             .unwrap()
@@ -56,12 +62,13 @@ mod tests {
 
             let result = timeout(
                 Duration::from_secs(5),
-                g.V(())
-                    .sample(25)
-                    .group(())
-                    .by(__.label())
-                    .by(__.propertyMap(()))
-                    .to_list(&client),
+                client.execute(
+                    g.V(())
+                        .sample(25)
+                        .group(())
+                        .by(__.label())
+                        .by(__.propertyMap(())),
+                ),
             )
             .await
             .unwrap()
@@ -76,12 +83,13 @@ mod tests {
 
             let result = timeout(
                 Duration::from_secs(5),
-                g.V(())
-                    .sample(25)
-                    .group(())
-                    .by(__.label())
-                    .by(__.propertyMap(()))
-                    .to_list(&client),
+                client.execute(
+                    g.V(())
+                        .sample(25)
+                        .group(())
+                        .by(__.label())
+                        .by(__.propertyMap(())),
+                ),
             )
             .await
             .unwrap()
@@ -94,12 +102,13 @@ mod tests {
 
             let result = timeout(
                 Duration::from_secs(5),
-                g.E(())
-                    .sample(25)
-                    .group(())
-                    .by(__.label())
-                    .by(__.propertyMap(()))
-                    .to_list(&client),
+                client.execute(
+                    g.E(())
+                        .sample(25)
+                        .group(())
+                        .by(__.label())
+                        .by(__.propertyMap(())),
+                ),
             )
             .await
             .unwrap()
@@ -110,19 +119,19 @@ mod tests {
             let v: gson::GsonV2 = result.parse().unwrap().remove(0);
             println!("{:?}", v);
 
-            let result = g
-                .V(())
-                .outE(())
-                .inV(())
-                .path()
-                .limit(5)
-                .to_list(&client)
+            let result = client
+                .execute(g.V(()).outE(()).inV().path().limit(5))
                 .await
                 .unwrap();
             println!(
                 "path response:\n{}",
                 from_utf8(result.0.get(0).unwrap()).unwrap()
             );
+
+            println!("testing typed traversal results");
+            let count: Vec<i64> = g.V(()).count().to_list(&client).await.unwrap();
+            let vertex: Option<Vertex> = g.V(()).next(&client).await.unwrap();
+            println!("{:?} {:?}", count, vertex);
         } else {
             println!("integration test not run, missing TEST_URL env var")
         }
@@ -1,13 +1,32 @@
 use serde::*;
 mod anonymous;
+mod args;
 mod traversal;
 use lazy_static::*;
 pub mod bytecode;
+mod element;
+pub mod parser;
+mod repository;
 mod source;
+mod strategy;
+mod template;
+pub mod translator;
+mod upsert;
+pub mod validator;
 
 use anonymous::AnonymousTraversal;
-use source::TraversalSource;
+pub use args::*;
+use bytecode::BytecodeStep;
+pub use element::*;
+pub use repository::*;
+pub use source::TraversalSource;
+pub use strategy::*;
+pub use template::*;
 pub use traversal::*;
+pub use upsert::*;
+
+#[cfg(feature = "macros")]
+pub use gremlin_rs_macros::{GremlinEdge, GremlinVertex};
 
 use crate::structure::gson::*;
 
@@ -24,71 +43,145 @@ pub enum Cardinality {
     Single,
 }
 
+// the sack and fold reducers, e.g. withSack(1.0, Operator.sum) or sack(Operator.mult).by('weight')
 #[derive(Serialize, Debug, Clone, Deserialize, PartialEq, Eq, Hash)]
-#[serde(rename_all = "lowercase")]
+#[serde(rename_all = "camelCase")]
 pub enum Operator {
-    Abs,
-    Acos,
-    Asin,
-    Atan,
-    Cbrt,
-    Ceil,
-    Cos,
-    Cosh,
-    Exp,
-    Floor,
-    Log,
-    Log10,
-    Log2,
-    Sin,
-    Sinh,
-    Sqrt,
-    Tan,
-    Tanh,
-    Signum,
+    Sum,
+    Minus,
+    Mult,
+    Div,
+    Min,
+    Max,
+    Assign,
+    And,
+    Or,
+    AddAll,
+    SumLong,
 }
 
 #[derive(Serialize, Debug, Clone, Deserialize)]
 #[serde(tag = "predicate", content = "value")]
 #[serde(rename_all = "lowercase")]
 pub enum P {
-    Eq(GsonNumber),
-    Neq(GsonNumber),
-    Lt(GsonNumber),
-    Lte(GsonNumber),
-    Gt(GsonNumber),
-    Gte(GsonNumber),
+    Eq(PValue),
+    Neq(PValue),
+    Lt(PValue),
+    Lte(PValue),
+    Gt(PValue),
+    Gte(PValue),
     Inside(RangeInput),
     Outside(RangeInput),
     Between(RangeInput),
-    // Within(Vec<GsonV2>),
-    // Without(Vec<GsonV2>),
+    Within(Vec<GsonV2>),
+    Without(Vec<GsonV2>),
+    And(Vec<GsonV2>),
+    Or(Vec<GsonV2>),
+    Not(PValue),
 }
 
-#[derive(Serialize, Debug, Clone, Deserialize)]
-pub struct RangeInput((GsonNumber, GsonNumber));
+impl P {
+    pub fn eq<V: Into<GsonV2>>(v: V) -> Self {
+        Self::Eq(v.into().into())
+    }
+
+    pub fn neq<V: Into<GsonV2>>(v: V) -> Self {
+        Self::Neq(v.into().into())
+    }
+
+    pub fn lt<V: Into<GsonV2>>(v: V) -> Self {
+        Self::Lt(v.into().into())
+    }
+
+    pub fn lte<V: Into<GsonV2>>(v: V) -> Self {
+        Self::Lte(v.into().into())
+    }
+
+    pub fn gt<V: Into<GsonV2>>(v: V) -> Self {
+        Self::Gt(v.into().into())
+    }
+
+    pub fn gte<V: Into<GsonV2>>(v: V) -> Self {
+        Self::Gte(v.into().into())
+    }
+
+    pub fn inside<A: Into<GsonV2>, B: Into<GsonV2>>(a: A, b: B) -> Self {
+        Self::Inside((a, b).into())
+    }
 
-impl From<(f64, f64)> for RangeInput {
-    fn from(f: (f64, f64)) -> Self {
-        Self((f.0.into(), f.1.into()))
+    pub fn outside<A: Into<GsonV2>, B: Into<GsonV2>>(a: A, b: B) -> Self {
+        Self::Outside((a, b).into())
+    }
+
+    pub fn between<A: Into<GsonV2>, B: Into<GsonV2>>(a: A, b: B) -> Self {
+        Self::Between((a, b).into())
+    }
+
+    // accepts a single value, a tuple of values or a Vec
+    pub fn within<V: Into<BytecodeStep>>(values: V) -> Self {
+        Self::Within(values.into().0)
+    }
+
+    pub fn without<V: Into<BytecodeStep>>(values: V) -> Self {
+        Self::Without(values.into().0)
+    }
+
+    pub fn not<V: Into<GsonV2>>(predicate: V) -> Self {
+        Self::Not(predicate.into().into())
+    }
+
+    pub fn and<V: Into<GsonV2>>(self, other: V) -> Self {
+        Self::And(vec![self.into(), other.into()])
+    }
+
+    pub fn or<V: Into<GsonV2>>(self, other: V) -> Self {
+        Self::Or(vec![self.into(), other.into()])
+    }
+
+    pub fn negate(self) -> Self {
+        match self {
+            Self::Eq(v) => Self::Neq(v),
+            Self::Neq(v) => Self::Eq(v),
+            Self::Lt(v) => Self::Gte(v),
+            Self::Lte(v) => Self::Gt(v),
+            Self::Gt(v) => Self::Lte(v),
+            Self::Gte(v) => Self::Lt(v),
+            Self::Within(v) => Self::Without(v),
+            Self::Without(v) => Self::Within(v),
+            Self::And(v) => Self::Or(v.into_iter().map(negate_gson).collect()),
+            Self::Or(v) => Self::And(v.into_iter().map(negate_gson).collect()),
+            Self::Not(v) => match *v.0 {
+                GsonV2::Predicate(p) => p,
+                v => Self::Not(negate_gson(v).into()),
+            },
+            p @ (Self::Inside(_) | Self::Outside(_) | Self::Between(_)) => Self::not(p),
+        }
     }
 }
 
-impl From<(f32, f32)> for RangeInput {
-    fn from(f: (f32, f32)) -> Self {
-        Self((f.0.into(), f.1.into()))
+fn negate_gson(v: GsonV2) -> GsonV2 {
+    match v {
+        GsonV2::Predicate(p) => p.negate().into(),
+        GsonV2::TextPredicate(p) => p.negate().into(),
+        v => P::Not(v.into()).into(),
     }
 }
 
-impl From<(i64, i64)> for RangeInput {
-    fn from(f: (i64, i64)) -> Self {
-        Self((f.0.into(), f.1.into()))
+#[derive(Serialize, Debug, Clone, Deserialize)]
+pub struct PValue(pub Box<GsonV2>);
+
+impl<T: Into<GsonV2>> From<T> for PValue {
+    fn from(v: T) -> Self {
+        Self(Box::new(v.into()))
     }
 }
 
-impl From<(i32, i32)> for RangeInput {
-    fn from(f: (i32, i32)) -> Self {
-        Self((f.0.into(), f.1.into()))
+#[derive(Serialize, Debug, Clone, Deserialize)]
+pub struct RangeInput((PValue, PValue));
+
+impl<A: Into<GsonV2>, B: Into<GsonV2>> From<(A, B)> for RangeInput {
+    fn from(f: (A, B)) -> Self {
+        Self((f.0.into().into(), f.1.into().into()))
     }
 }
 
@@ -102,6 +195,65 @@ pub enum TextP {
     NotStartingWith(String),
     NotEndingWith(String),
     NotContaining(String),
+    Regex(String),
+    NotRegex(String),
+}
+
+impl TextP {
+    pub fn startingWith<S: Into<String>>(s: S) -> Self {
+        Self::StartingWith(s.into())
+    }
+
+    pub fn endingWith<S: Into<String>>(s: S) -> Self {
+        Self::EndingWith(s.into())
+    }
+
+    pub fn containing<S: Into<String>>(s: S) -> Self {
+        Self::Containing(s.into())
+    }
+
+    pub fn notStartingWith<S: Into<String>>(s: S) -> Self {
+        Self::NotStartingWith(s.into())
+    }
+
+    pub fn notEndingWith<S: Into<String>>(s: S) -> Self {
+        Self::NotEndingWith(s.into())
+    }
+
+    pub fn notContaining<S: Into<String>>(s: S) -> Self {
+        Self::NotContaining(s.into())
+    }
+
+    // java.util.regex syntax, prefix the pattern with (?i) for case-insensitive matching
+    pub fn regex<S: Into<String>>(s: S) -> Self {
+        Self::Regex(s.into())
+    }
+
+    pub fn notRegex<S: Into<String>>(s: S) -> Self {
+        Self::NotRegex(s.into())
+    }
+
+    // composed text predicates are serialized as g:P, as in TinkerPop
+    pub fn and<V: Into<GsonV2>>(self, other: V) -> P {
+        P::And(vec![self.into(), other.into()])
+    }
+
+    pub fn or<V: Into<GsonV2>>(self, other: V) -> P {
+        P::Or(vec![self.into(), other.into()])
+    }
+
+    pub fn negate(self) -> Self {
+        match self {
+            Self::StartingWith(s) => Self::NotStartingWith(s),
+            Self::EndingWith(s) => Self::NotEndingWith(s),
+            Self::Containing(s) => Self::NotContaining(s),
+            Self::NotStartingWith(s) => Self::StartingWith(s),
+            Self::NotEndingWith(s) => Self::EndingWith(s),
+            Self::NotContaining(s) => Self::Containing(s),
+            Self::Regex(s) => Self::NotRegex(s),
+            Self::NotRegex(s) => Self::Regex(s),
+        }
+    }
 }
 
 #[derive(Serialize, Debug, Clone, Deserialize, PartialEq, Eq, Hash)]
@@ -114,8 +266,22 @@ pub enum Order {
 
 #[derive(Serialize, Debug, Clone, Deserialize)]
 pub struct Binding {
-    key: String,
-    value: Box<GsonV2>,
+    pub key: String,
+    pub value: Box<GsonV2>,
+}
+
+impl Binding {
+    pub fn of<K: Into<String>, V: Into<GsonV2>>(key: K, value: V) -> Self {
+        Self {
+            key: key.into(),
+            value: Box::new(value.into()),
+        }
+    }
+
+    // a named placeholder, its value is set per execution by Template::bind
+    pub fn var<K: Into<String>>(key: K) -> Self {
+        Self::of(key, GsonV2::Null)
+    }
 }
 
 #[derive(Serialize, Debug, Clone, Deserialize, PartialEq, Eq, Hash)]
@@ -165,6 +331,62 @@ pub enum Merge {
     OnMatch,
 }
 
+#[derive(Serialize, Debug, Clone, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum Column {
+    Keys,
+    Values,
+}
+
+#[derive(Serialize, Debug, Clone, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub enum Barrier {
+    NormSack,
+}
+
+// units of dateAdd and dateDiff
+#[derive(Serialize, Debug, Clone, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum DT {
+    Second,
+    Minute,
+    Hour,
+    Day,
+}
+
+// the with() configuration of valueMap, propertyMap and index. GraphSON has no type for these,
+// they are sent as the plain strings and numbers TinkerPop defines, so only the gremlin names
+// deserialize (for the parser)
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum WithOptions {
+    Tokens,
+    Indexer,
+    None,
+    Ids,
+    Labels,
+    Keys,
+    Values,
+    All,
+    List,
+    Map,
+}
+
+impl From<WithOptions> for GsonV2 {
+    fn from(o: WithOptions) -> Self {
+        match o {
+            WithOptions::Tokens => "~tinkerpop.valueMap.tokens".into(),
+            WithOptions::Indexer => "~tinkerpop.index.indexer".into(),
+            WithOptions::None | WithOptions::List => GsonV2::Integer(0),
+            WithOptions::Ids | WithOptions::Map => GsonV2::Integer(1),
+            WithOptions::Labels => GsonV2::Integer(2),
+            WithOptions::Keys => GsonV2::Integer(4),
+            WithOptions::Values => GsonV2::Integer(8),
+            WithOptions::All => GsonV2::Integer(15),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -182,4 +404,294 @@ mod tests {
         let b: bytecode::Bytecode = qry.into();
         println!("{}", to_string_pretty(&b).unwrap());
     }
+
+    #[test]
+    fn source_instructions() {
+        let b: bytecode::Bytecode = g
+            .withSack(1.0)
+            .withSideEffect(("x", 0))
+            .V(())
+            .sack(())
+            .into();
+        let v = serde_json::to_value(&b).unwrap();
+        assert_eq!(v["source"][0][0], "withSack");
+        assert_eq!(v["source"][1][0], "withSideEffect");
+        assert_eq!(v["step"][0][0], "V");
+
+        let b: bytecode::Bytecode = g.V(()).into();
+        assert!(serde_json::to_value(&b).unwrap().get("source").is_none());
+    }
+
+    #[test]
+    fn strategy_serialization() {
+        let b: bytecode::Bytecode = g
+            .withStrategies((
+                ReadOnlyStrategy,
+                SubgraphStrategy {
+                    vertices: Some(__.hasLabel("person")),
+                    ..Default::default()
+                },
+            ))
+            .V(())
+            .into();
+        let v = serde_json::to_value(&b).unwrap();
+        let strategies = &v["source"][0];
+        assert_eq!(strategies[0], "withStrategies");
+        assert_eq!(strategies[1]["@type"], "g:ReadOnlyStrategy");
+        assert_eq!(strategies[2]["@type"], "g:SubgraphStrategy");
+        assert_eq!(strategies[2]["@value"]["vertices"]["@type"], "g:Bytecode");
+    }
+
+    #[test]
+    fn traversal_composition() {
+        fn active_users<S, E>(t: Traversal<S, E>) -> Traversal<S, E> {
+            t.hasLabel("user").has(("active", true))
+        }
+
+        let prefix = g.V(()).apply(active_users);
+        let names: bytecode::Bytecode = prefix.clone().values("name").into();
+        let count: bytecode::Bytecode = prefix.append(__.out("knows")).count().into();
+
+        assert_eq!(names.steps().len(), 4);
+        assert_eq!(count.steps().len(), 5);
+        assert_eq!(
+            serde_json::to_value(&count.steps()[3]).unwrap(),
+            serde_json::json!(["out", "knows"])
+        );
+
+        // the appended traversal keeps its strategies, shared ones aren't repeated
+        let read_only = g.withStrategies(TraversalStrategy::new("ReadOnlyStrategy"));
+        let merged: bytecode::Bytecode = read_only
+            .V(())
+            .append(
+                read_only
+                    .withoutStrategies("LazyBarrierStrategy")
+                    .V(())
+                    .out("knows"),
+            )
+            .into();
+        let merged = serde_json::to_value(&merged).unwrap();
+        assert_eq!(merged["source"].as_array().unwrap().len(), 2);
+        assert_eq!(merged["source"][1][0], "withoutStrategies");
+        assert_eq!(merged["step"].as_array().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn predicate_serialization() {
+        let p = |p: P| serde_json::to_value(GsonV2::from(p)).unwrap();
+
+        assert_eq!(
+            p(P::eq("alice")),
+            serde_json::json!({"@type": "g:P", "@value": {"predicate": "eq", "value": "alice"}})
+        );
+        assert_eq!(
+            p(P::within(("a", "b")))["@value"],
+            serde_json::json!({"predicate": "within", "value": ["a", "b"]})
+        );
+        assert_eq!(
+            p(P::within(vec!["a", "b"]))["@value"]["value"],
+            serde_json::json!(["a", "b"])
+        );
+
+        let composed = p(P::gt(1).and(P::lt(10)).or(P::eq(100)));
+        assert_eq!(composed["@value"]["predicate"], "or");
+        assert_eq!(composed["@value"]["value"][0]["@value"]["predicate"], "and");
+        assert_eq!(
+            composed["@value"]["value"][0]["@value"]["value"][1]["@value"]["predicate"],
+            "lt"
+        );
+
+        let negated = p(P::gt(1).and(P::within(("a",))).negate());
+        assert_eq!(negated["@value"]["predicate"], "or");
+        assert_eq!(negated["@value"]["value"][0]["@value"]["predicate"], "lte");
+        assert_eq!(
+            negated["@value"]["value"][1]["@value"]["predicate"],
+            "without"
+        );
+
+        let between = p(P::between(1, 5).negate());
+        assert_eq!(between["@value"]["predicate"], "not");
+        assert_eq!(between["@value"]["value"]["@value"]["predicate"], "between");
+    }
+
+    #[test]
+    fn text_predicate_serialization() {
+        let p = |p: GsonV2| serde_json::to_value(p).unwrap();
+
+        assert_eq!(
+            p(TextP::regex("(?i)^al").into()),
+            serde_json::json!({"@type": "g:TextP", "@value": {"predicate": "regex", "value": "(?i)^al"}})
+        );
+
+        let mixed = p(TextP::startingWith("a").or(P::eq("bob")).into());
+        assert_eq!(mixed["@type"], "g:P");
+        assert_eq!(mixed["@value"]["predicate"], "or");
+        assert_eq!(mixed["@value"]["value"][0]["@type"], "g:TextP");
+        assert_eq!(mixed["@value"]["value"][1]["@type"], "g:P");
+
+        let negated = p(TextP::containing("x")
+            .and(TextP::notRegex("y"))
+            .negate()
+            .into());
+        assert_eq!(negated["@value"]["predicate"], "or");
+        assert_eq!(
+            negated["@value"]["value"][0]["@value"]["predicate"],
+            "notContaining"
+        );
+        assert_eq!(
+            negated["@value"]["value"][1]["@value"]["predicate"],
+            "regex"
+        );
+    }
+
+    #[test]
+    fn typed_steps() {
+        let b: bytecode::Bytecode = g
+            .V(())
+            .has(("person", "name", P::within(("marko", "josh"))))
+            .repeat(__.out("knows"))
+            .times(2)
+            .order()
+            .by(("age", Order::Desc))
+            .limit((Scope::Local, 2))
+            .toE(Direction::Out, ("created", "knows"))
+            .step("none", ())
+            .into();
+        let v = serde_json::to_value(b.steps()).unwrap();
+
+        assert_eq!(v[2][1]["@type"], "g:Bytecode");
+        assert_eq!(
+            v[3],
+            serde_json::json!(["times", {"@type": "g:Int32", "@value": 2}])
+        );
+        assert_eq!(v[5][2]["@type"], "g:Order");
+        assert_eq!(
+            v[6][2],
+            serde_json::json!({"@type": "g:Int64", "@value": 2})
+        );
+        assert_eq!(
+            v[7],
+            serde_json::json!(["toE", {"@type": "g:Direction", "@value": "OUT"}, "created", "knows"])
+        );
+        assert_eq!(v[8], serde_json::json!(["none"]));
+    }
+
+    #[test]
+    fn mutating_traversals() {
+        assert!(!g.V(()).hasLabel("user").out("knows").is_mutating());
+        assert!(g.V("USER_ID").property(("name", "bob")).is_mutating());
+        assert!(g
+            .V(())
+            .has(("user", "email", "a@b.c"))
+            .fold()
+            .coalesce((__.unfold(), __.addV("user")))
+            .is_mutating());
+    }
+
+    #[test]
+    fn typed_traversals() {
+        use crate::structure::{Edge, Path, Vertex};
+        use std::collections::HashMap;
+
+        let _: Traversal<Vertex, i64> = g.V(()).count();
+        let _: Traversal<Vertex, GsonV2> = g.V(()).values("name");
+        let _: Traversal<Vertex, String> = g.V(()).out("knows").label();
+        let _: Traversal<Vertex, Edge> = g.V(()).outE(()).has(("weight", 1.0));
+        let _: Traversal<Edge, Vec<Vertex>> = g.E(()).inV().fold();
+        let _: Traversal<Vertex, HashMap<String, GsonV2>> = g.V(()).valueMap(());
+        let _: Traversal<Vertex, Path> = g.V(()).out(()).path();
+        let _: Traversal<Vertex, i64> = g.V(()).local(__.out(()).count());
+        let _: Traversal<GsonV2, String> = g.inject("a").cast();
+        let _: Traversal<GsonV2, i64> = __.out(()).count();
+    }
+
+    #[test]
+    fn map_arguments() {
+        use crate::structure::de::from_str;
+        use serde_json::{json, to_value};
+        use std::collections::{BTreeMap, HashMap};
+
+        let search = GsonMap::new()
+            .with(T::Label, "person")
+            .with("name", "marko");
+        let b: bytecode::Bytecode = g
+            .mergeV(search)
+            .option((Merge::OnCreate, HashMap::from([("age", 29)])))
+            .into();
+        let steps = to_value(&b).unwrap();
+        assert_eq!(
+            steps["step"][0],
+            json!(["mergeV", {"@type": "g:Map", "@value": [
+                {"@type": "g:T", "@value": "label"}, "person", "name", "marko"
+            ]}])
+        );
+        // string keyed maps stay plain objects
+        assert_eq!(
+            steps["step"][1][2],
+            json!({"age": {"@type": "g:Int32", "@value": 29}})
+        );
+
+        let sorted = GsonV2::from(BTreeMap::from([("b", 2i64), ("a", 1i64)]));
+        assert_eq!(
+            serde_json::to_string(&sorted).unwrap(),
+            r#"{"a":{"@type":"g:Int64","@value":1},"b":{"@type":"g:Int64","@value":2}}"#
+        );
+
+        let json = serde_json::to_string(&GsonV2::from(HashMap::from([(Direction::In, 2i64)])));
+        match from_str::<GsonV2>(&json.unwrap()).unwrap() {
+            GsonV2::GMap(m) => {
+                assert_eq!(m.len(), 1);
+                assert!(matches!(
+                    m.iter().next(),
+                    Some((GsonV2::Direction(Direction::In), GsonV2::Long(2)))
+                ));
+            }
+            v => panic!("expected a g:Map, got {:?}", v),
+        }
+        assert!(from_str::<GsonV2>(r#"{"@type":"g:Map","@value":["a"]}"#).is_err());
+    }
+
+    #[test]
+    fn token_serialization() {
+        use crate::structure::de::from_str;
+        use serde_json::{json, to_value};
+
+        let b: bytecode::Bytecode = g
+            .withSack((1.0, Operator::Sum))
+            .V(())
+            .sack(Operator::AddAll)
+            .barrier(Barrier::NormSack)
+            .group(())
+            .by(T::Label)
+            .select(Column::Keys)
+            .valueMap(())
+            .with((WithOptions::Tokens, WithOptions::Labels))
+            .into();
+        let v = to_value(&b).unwrap();
+        assert_eq!(
+            v["source"][0][2],
+            json!({"@type": "g:Operator", "@value": "sum"})
+        );
+        assert_eq!(
+            v["step"][1][1],
+            json!({"@type": "g:Operator", "@value": "addAll"})
+        );
+        assert_eq!(
+            v["step"][2][1],
+            json!({"@type": "g:Barrier", "@value": "normSack"})
+        );
+        assert_eq!(
+            v["step"][5][1],
+            json!({"@type": "g:Column", "@value": "keys"})
+        );
+        assert_eq!(
+            v["step"][7],
+            json!(["with", "~tinkerpop.valueMap.tokens", {"@type": "g:Int32", "@value": 2}])
+        );
+
+        let v = from_str::<GsonV2>(r#"{"@type":"g:DT","@value":"day"}"#).unwrap();
+        assert!(matches!(v, GsonV2::DT(DT::Day)));
+        let v = from_str::<GsonV2>(r#"{"@type":"g:Operator","@value":"sumLong"}"#).unwrap();
+        assert!(matches!(v, GsonV2::Operator(Operator::SumLong)));
+    }
 }
@@ -1,5 +1,9 @@
 use super::*;
-use crate::process::bytecode::BytecodeStep;
+use crate::{
+    process::bytecode::BytecodeStep,
+    structure::{gson::GsonV2, Edge, Path, Vertex},
+};
+use std::collections::HashMap;
 
 pub struct AnonymousTraversal {
     traversal: Traversal,
@@ -12,91 +16,95 @@ impl AnonymousTraversal {
         }
     }
 
-    pub fn V<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Traversal {
+    pub fn step<A: Into<BytecodeStep>>(&self, op: &str, args: A) -> Traversal {
+        self.traversal.clone().step(op, args)
+    }
+
+    pub fn V<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Traversal<GsonV2, Vertex> {
         self.traversal.clone().V(args)
     }
 
-    pub fn addE<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Traversal {
+    pub fn addE<A: Label>(&self, args: A) -> Traversal<GsonV2, Edge> {
         self.traversal.clone().addE(args)
     }
 
-    pub fn addV<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Traversal {
+    pub fn addV<A: OptionalLabel>(&self, args: A) -> Traversal<GsonV2, Vertex> {
         self.traversal.clone().addV(args)
     }
 
-    pub fn aggregate<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Traversal {
+    pub fn aggregate<A: SideEffectKey>(&self, args: A) -> Traversal {
         self.traversal.clone().aggregate(args)
     }
 
-    pub fn and<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Traversal {
+    pub fn and<A: Traversals>(&self, args: A) -> Traversal {
         self.traversal.clone().and(args)
     }
 
-    pub fn as_<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Traversal {
+    pub fn as_<A: Labels>(&self, args: A) -> Traversal {
         self.traversal.clone().as_(args)
     }
 
-    pub fn barrier<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Traversal {
+    pub fn barrier<A: BarrierArgs>(&self, args: A) -> Traversal {
         self.traversal.clone().barrier(args)
     }
 
-    pub fn both<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Traversal {
+    pub fn both<A: Keys>(&self, args: A) -> Traversal<GsonV2, Vertex> {
         self.traversal.clone().both(args)
     }
 
-    pub fn bothE<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Traversal {
+    pub fn bothE<A: Keys>(&self, args: A) -> Traversal<GsonV2, Edge> {
         self.traversal.clone().bothE(args)
     }
 
-    pub fn bothV<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Traversal {
-        self.traversal.clone().bothV(args)
+    pub fn bothV(&self) -> Traversal<GsonV2, Vertex> {
+        self.traversal.clone().bothV()
     }
 
-    pub fn branch(&self) -> Traversal {
-        self.traversal.clone().branch()
+    pub fn branch<S, E>(&self, traversal: Traversal<S, E>) -> Traversal<GsonV2, GsonV2> {
+        self.traversal.clone().branch(traversal)
     }
 
-    pub fn by<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Traversal {
+    pub fn by<A: ByArgs>(&self, args: A) -> Traversal {
         self.traversal.clone().by(args)
     }
 
-    pub fn call<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Traversal {
+    pub fn call<A: CallArgs>(&self, args: A) -> Traversal<GsonV2, GsonV2> {
         self.traversal.clone().call(args)
     }
 
-    pub fn cap<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Traversal {
+    pub fn cap<A: Labels>(&self, args: A) -> Traversal<GsonV2, GsonV2> {
         self.traversal.clone().cap(args)
     }
 
-    pub fn choose<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Traversal {
+    pub fn choose<A: ChooseArgs>(&self, args: A) -> Traversal<GsonV2, GsonV2> {
         self.traversal.clone().choose(args)
     }
 
-    pub fn coalesce<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Traversal {
+    pub fn coalesce<A: Traversals>(&self, args: A) -> Traversal<GsonV2, GsonV2> {
         self.traversal.clone().coalesce(args)
     }
 
-    pub fn coin<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Traversal {
-        self.traversal.clone().coin(args)
+    pub fn coin(&self, probability: f64) -> Traversal {
+        self.traversal.clone().coin(probability)
     }
 
-    pub fn connectedComponent<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Traversal {
-        self.traversal.clone().connectedComponent(args)
+    pub fn connectedComponent(&self) -> Traversal {
+        self.traversal.clone().connectedComponent()
     }
 
-    pub fn constant<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Traversal {
-        self.traversal.clone().constant(args)
+    pub fn constant<V: Into<GsonV2>>(&self, value: V) -> Traversal<GsonV2, GsonV2> {
+        self.traversal.clone().constant(value)
     }
 
-    pub fn count(&self) -> Traversal {
+    pub fn count(&self) -> Traversal<GsonV2, i64> {
         self.traversal.clone().count()
     }
 
-    pub fn cyclicPath<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Traversal {
-        self.traversal.clone().cyclicPath(args)
+    pub fn cyclicPath(&self) -> Traversal {
+        self.traversal.clone().cyclicPath()
     }
 
-    pub fn dedup<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Traversal {
+    pub fn dedup<A: DedupArgs>(&self, args: A) -> Traversal {
         self.traversal.clone().dedup(args)
     }
 
@@ -104,15 +112,15 @@ impl AnonymousTraversal {
         self.traversal.clone().drop()
     }
 
-    pub fn element(&self) -> Traversal {
+    pub fn element(&self) -> Traversal<GsonV2, GsonV2> {
         self.traversal.clone().element()
     }
 
-    pub fn elementMap<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Traversal {
+    pub fn elementMap<A: Keys>(&self, args: A) -> Traversal<GsonV2, HashMap<String, GsonV2>> {
         self.traversal.clone().elementMap(args)
     }
 
-    pub fn emit<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Traversal {
+    pub fn emit<A: EmitArgs>(&self, args: A) -> Traversal {
         self.traversal.clone().emit(args)
     }
 
@@ -120,31 +128,31 @@ impl AnonymousTraversal {
         self.traversal.clone().fail()
     }
 
-    pub fn filter<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Traversal {
-        self.traversal.clone().filter(args)
+    pub fn filter<S, E>(&self, traversal: Traversal<S, E>) -> Traversal {
+        self.traversal.clone().filter(traversal)
     }
 
-    pub fn flatMap(&self) -> Traversal {
-        self.traversal.clone().flatMap()
+    pub fn flatMap<S, E>(&self, traversal: Traversal<S, E>) -> Traversal<GsonV2, E> {
+        self.traversal.clone().flatMap(traversal)
     }
 
-    pub fn fold(&self) -> Traversal {
+    pub fn fold(&self) -> Traversal<GsonV2, Vec<GsonV2>> {
         self.traversal.clone().fold()
     }
 
-    pub fn from<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Traversal {
+    pub fn from<A: FromArgs>(&self, args: A) -> Traversal {
         self.traversal.clone().from(args)
     }
 
-    pub fn group<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Traversal {
+    pub fn group<A: OptionalKey>(&self, args: A) -> Traversal<GsonV2, GsonV2> {
         self.traversal.clone().group(args)
     }
 
-    pub fn groupCount<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Traversal {
+    pub fn groupCount<A: OptionalKey>(&self, args: A) -> Traversal<GsonV2, GsonV2> {
         self.traversal.clone().groupCount(args)
     }
 
-    pub fn has<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Traversal {
+    pub fn has<A: HasArgs>(&self, args: A) -> Traversal {
         self.traversal.clone().has(args)
     }
 
@@ -152,23 +160,23 @@ impl AnonymousTraversal {
         self.traversal.clone().hasId(args)
     }
 
-    pub fn hasKey<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Traversal {
+    pub fn hasKey<A: KeysOrPredicate>(&self, args: A) -> Traversal {
         self.traversal.clone().hasKey(args)
     }
 
-    pub fn hasLabel<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Traversal {
+    pub fn hasLabel<A: KeysOrPredicate>(&self, args: A) -> Traversal {
         self.traversal.clone().hasLabel(args)
     }
 
-    pub fn hasNot<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Traversal {
-        self.traversal.clone().hasNot(args)
+    pub fn hasNot<K: StrArg>(&self, key: K) -> Traversal {
+        self.traversal.clone().hasNot(key)
     }
 
     pub fn hasValue<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Traversal {
         self.traversal.clone().hasValue(args)
     }
 
-    pub fn id(&self) -> Traversal {
+    pub fn id(&self) -> Traversal<GsonV2, GsonV2> {
         self.traversal.clone().id()
     }
 
@@ -176,218 +184,218 @@ impl AnonymousTraversal {
         self.traversal.clone().identity()
     }
 
-    pub fn in_<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Traversal {
+    pub fn in_<A: Keys>(&self, args: A) -> Traversal<GsonV2, Vertex> {
         self.traversal.clone().in_(args)
     }
 
-    pub fn inE<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Traversal {
+    pub fn inE<A: Keys>(&self, args: A) -> Traversal<GsonV2, Edge> {
         self.traversal.clone().inE(args)
     }
 
-    pub fn inV<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Traversal {
-        self.traversal.clone().inV(args)
+    pub fn inV(&self) -> Traversal<GsonV2, Vertex> {
+        self.traversal.clone().inV()
     }
 
-    pub fn index(&self) -> Traversal {
+    pub fn index(&self) -> Traversal<GsonV2, GsonV2> {
         self.traversal.clone().index()
     }
 
-    pub fn inject<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Traversal {
+    pub fn inject<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Traversal<GsonV2, GsonV2> {
         self.traversal.clone().inject(args)
     }
 
-    pub fn is<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Traversal {
-        self.traversal.clone().is(args)
+    pub fn is<V: Into<GsonV2>>(&self, value: V) -> Traversal {
+        self.traversal.clone().is(value)
     }
 
-    pub fn key(&self) -> Traversal {
+    pub fn key(&self) -> Traversal<GsonV2, String> {
         self.traversal.clone().key()
     }
 
-    pub fn label(&self) -> Traversal {
+    pub fn label(&self) -> Traversal<GsonV2, String> {
         self.traversal.clone().label()
     }
 
-    pub fn limit<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Traversal {
+    pub fn limit<A: LimitArgs>(&self, args: A) -> Traversal {
         self.traversal.clone().limit(args)
     }
 
-    pub fn local<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Traversal {
-        self.traversal.clone().local(args)
+    pub fn local<S, E>(&self, traversal: Traversal<S, E>) -> Traversal<GsonV2, E> {
+        self.traversal.clone().local(traversal)
     }
 
-    pub fn loops(&self) -> Traversal {
+    pub fn loops(&self) -> Traversal<GsonV2, GsonV2> {
         self.traversal.clone().loops()
     }
 
-    pub fn map<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Traversal {
-        self.traversal.clone().map(args)
+    pub fn map<S, E>(&self, traversal: Traversal<S, E>) -> Traversal<GsonV2, E> {
+        self.traversal.clone().map(traversal)
     }
 
-    pub fn match_<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Traversal {
+    pub fn match_<A: Traversals>(&self, args: A) -> Traversal {
         self.traversal.clone().match_(args)
     }
 
-    pub fn math<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Traversal {
-        self.traversal.clone().math(args)
+    pub fn math<K: StrArg>(&self, expression: K) -> Traversal<GsonV2, f64> {
+        self.traversal.clone().math(expression)
     }
 
-    pub fn max(&self) -> Traversal {
+    pub fn max(&self) -> Traversal<GsonV2, GsonV2> {
         self.traversal.clone().max()
     }
-    pub fn mean(&self) -> Traversal {
+    pub fn mean(&self) -> Traversal<GsonV2, f64> {
         self.traversal.clone().mean()
     }
-    pub fn mergeE<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Traversal {
+    pub fn mergeE<A: MergeArgs>(&self, args: A) -> Traversal<GsonV2, Edge> {
         self.traversal.clone().mergeE(args)
     }
-    pub fn mergeV<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Traversal {
+    pub fn mergeV<A: MergeArgs>(&self, args: A) -> Traversal<GsonV2, Vertex> {
         self.traversal.clone().mergeV(args)
     }
-    pub fn min(&self) -> Traversal {
+    pub fn min(&self) -> Traversal<GsonV2, GsonV2> {
         self.traversal.clone().min()
     }
     pub fn none(&self) -> Traversal {
         self.traversal.clone().none()
     }
-    pub fn not<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Traversal {
-        self.traversal.clone().not(args)
+    pub fn not<S, E>(&self, traversal: Traversal<S, E>) -> Traversal {
+        self.traversal.clone().not(traversal)
     }
 
-    pub fn option<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Traversal {
+    pub fn option<A: OptionArgs>(&self, args: A) -> Traversal {
         self.traversal.clone().option(args)
     }
-    pub fn or<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Traversal {
+    pub fn or<A: Traversals>(&self, args: A) -> Traversal {
         self.traversal.clone().or(args)
     }
     pub fn order(&self) -> Traversal {
         self.traversal.clone().order()
     }
 
-    pub fn otherV<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Traversal {
-        self.traversal.clone().otherV(args)
+    pub fn otherV(&self) -> Traversal<GsonV2, Vertex> {
+        self.traversal.clone().otherV()
     }
-    pub fn out<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Traversal {
+    pub fn out<A: Keys>(&self, args: A) -> Traversal<GsonV2, Vertex> {
         self.traversal.clone().out(args)
     }
-    pub fn outE<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Traversal {
+    pub fn outE<A: Keys>(&self, args: A) -> Traversal<GsonV2, Edge> {
         self.traversal.clone().outE(args)
     }
 
-    pub fn outV<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Traversal {
-        self.traversal.clone().outV(args)
+    pub fn outV(&self) -> Traversal<GsonV2, Vertex> {
+        self.traversal.clone().outV()
     }
     pub fn pageRank(&self) -> Traversal {
         self.traversal.clone().pageRank()
     }
-    pub fn path(&self) -> Traversal {
+    pub fn path(&self) -> Traversal<GsonV2, Path> {
         self.traversal.clone().path()
     }
     pub fn peerPressure(&self) -> Traversal {
         self.traversal.clone().peerPressure()
     }
-    pub fn profile(&self) -> Traversal {
+    pub fn profile(&self) -> Traversal<GsonV2, GsonV2> {
         self.traversal.clone().profile()
     }
     pub fn program<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Traversal {
         self.traversal.clone().program(args)
     }
-    pub fn project<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Traversal {
+    pub fn project<A: Labels>(&self, args: A) -> Traversal<GsonV2, HashMap<String, GsonV2>> {
         self.traversal.clone().project(args)
     }
-    pub fn properties<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Traversal {
+    pub fn properties<A: Keys>(&self, args: A) -> Traversal<GsonV2, GsonV2> {
         self.traversal.clone().properties(args)
     }
-    pub fn property<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Traversal {
+    pub fn property<A: PropertyArgs>(&self, args: A) -> Traversal {
         self.traversal.clone().property(args)
     }
-    pub fn propertyMap<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Traversal {
+    pub fn propertyMap<A: Keys>(&self, args: A) -> Traversal<GsonV2, HashMap<String, GsonV2>> {
         self.traversal.clone().propertyMap(args)
     }
-    pub fn range<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Traversal {
+    pub fn range<A: RangeArgs>(&self, args: A) -> Traversal {
         self.traversal.clone().range(args)
     }
-    pub fn read<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Traversal {
-        self.traversal.clone().read(args)
+    pub fn read(&self) -> Traversal {
+        self.traversal.clone().read()
     }
-    pub fn repeat<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Traversal {
+    pub fn repeat<A: RepeatArgs>(&self, args: A) -> Traversal {
         self.traversal.clone().repeat(args)
     }
 
-    pub fn sack<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Traversal {
+    pub fn sack<A: SackArgs>(&self, args: A) -> Traversal {
         self.traversal.clone().sack(args)
     }
-    pub fn sample<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Traversal {
+    pub fn sample<A: SampleArgs>(&self, args: A) -> Traversal {
         self.traversal.clone().sample(args)
     }
-    pub fn select<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Traversal {
+    pub fn select<A: SelectArgs>(&self, args: A) -> Traversal<GsonV2, GsonV2> {
         self.traversal.clone().select(args)
     }
-    pub fn shortestPath<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Traversal {
-        self.traversal.clone().shortestPath(args)
+    pub fn shortestPath(&self) -> Traversal<GsonV2, Path> {
+        self.traversal.clone().shortestPath()
     }
-    pub fn sideEffect<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Traversal {
-        self.traversal.clone().sideEffect(args)
+    pub fn sideEffect<S, E>(&self, traversal: Traversal<S, E>) -> Traversal {
+        self.traversal.clone().sideEffect(traversal)
     }
-    pub fn simplePath<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Traversal {
-        self.traversal.clone().simplePath(args)
+    pub fn simplePath(&self) -> Traversal {
+        self.traversal.clone().simplePath()
     }
-    pub fn skip<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Traversal {
+    pub fn skip<A: LimitArgs>(&self, args: A) -> Traversal {
         self.traversal.clone().skip(args)
     }
-    pub fn store<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Traversal {
-        self.traversal.clone().store(args)
+    pub fn store<K: StrArg>(&self, key: K) -> Traversal {
+        self.traversal.clone().store(key)
     }
-    pub fn subgraph<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Traversal {
-        self.traversal.clone().subgraph(args)
+    pub fn subgraph<K: StrArg>(&self, key: K) -> Traversal {
+        self.traversal.clone().subgraph(key)
     }
-    pub fn sum(&self) -> Traversal {
+    pub fn sum(&self) -> Traversal<GsonV2, GsonV2> {
         self.traversal.clone().sum()
     }
-    pub fn tail<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Traversal {
+    pub fn tail<A: TailArgs>(&self, args: A) -> Traversal {
         self.traversal.clone().tail(args)
     }
-    pub fn timeLimit<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Traversal {
-        self.traversal.clone().timeLimit(args)
+    pub fn timeLimit(&self, millis: i64) -> Traversal {
+        self.traversal.clone().timeLimit(millis)
     }
-    pub fn times<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Traversal {
-        self.traversal.clone().times(args)
+    pub fn times(&self, max_loops: i32) -> Traversal {
+        self.traversal.clone().times(max_loops)
     }
-    pub fn to<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Traversal {
+    pub fn to<A: FromArgs>(&self, args: A) -> Traversal {
         self.traversal.clone().to(args)
     }
-    pub fn toE<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Traversal {
-        self.traversal.clone().toE(args)
+    pub fn toE<L: Keys>(&self, direction: Direction, labels: L) -> Traversal<GsonV2, Edge> {
+        self.traversal.clone().toE(direction, labels)
     }
-    pub fn toV<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Traversal {
-        self.traversal.clone().toV(args)
+    pub fn toV(&self, direction: Direction) -> Traversal<GsonV2, Vertex> {
+        self.traversal.clone().toV(direction)
     }
-    pub fn tree<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Traversal {
+    pub fn tree<A: OptionalKey>(&self, args: A) -> Traversal<GsonV2, GsonV2> {
         self.traversal.clone().tree(args)
     }
-    pub fn unfold(&self) -> Traversal {
+    pub fn unfold(&self) -> Traversal<GsonV2, GsonV2> {
         self.traversal.clone().unfold()
     }
-    pub fn union<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Traversal {
+    pub fn union<A: Traversals>(&self, args: A) -> Traversal<GsonV2, GsonV2> {
         self.traversal.clone().union(args)
     }
-    pub fn until<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Traversal {
+    pub fn until<A: Condition>(&self, args: A) -> Traversal {
         self.traversal.clone().until(args)
     }
-    pub fn value(&self) -> Traversal {
+    pub fn value(&self) -> Traversal<GsonV2, GsonV2> {
         self.traversal.clone().value()
     }
-    pub fn valueMap<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Traversal {
+    pub fn valueMap<A: ValueMapArgs>(&self, args: A) -> Traversal<GsonV2, HashMap<String, GsonV2>> {
         self.traversal.clone().valueMap(args)
     }
 
-    pub fn values<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Traversal {
+    pub fn values<A: Keys>(&self, args: A) -> Traversal<GsonV2, GsonV2> {
         self.traversal.clone().values(args)
     }
-    pub fn where_<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Traversal {
+    pub fn where_<A: WhereArgs>(&self, args: A) -> Traversal {
         self.traversal.clone().where_(args)
     }
-    pub fn with<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Traversal {
+    pub fn with<A: WithArgs>(&self, args: A) -> Traversal {
         self.traversal.clone().with(args)
     }
     pub fn write(&self) -> Traversal {
@@ -0,0 +1,296 @@
+use super::*;
+use crate::{
+    driver::GremlinExecutor,
+    process::bytecode::BytecodeStep,
+    structure::{gson::GsonV2, Vertex},
+};
+use std::fmt::Debug;
+
+// argument shapes accepted by the typed Traversal steps, each mirrors one or more of the
+// overloads in the gremlin reference, Traversal::step accepts any BytecodeStep
+
+pub trait StrArg: Into<GsonV2> + Debug + Clone {}
+
+impl StrArg for &str {}
+impl StrArg for String {}
+
+macro_rules! marker_impl {
+    ($Trait:ident: $($Ty:ty),+) => {
+        $(impl $Trait for $Ty {})+
+    };
+}
+
+// property keys, edge labels and side effect keys, possibly empty
+pub trait Keys: Into<BytecodeStep> {}
+
+// one or more step labels or keys
+pub trait Labels: Into<BytecodeStep> {}
+
+impl<S: StrArg> Keys for Vec<S> {}
+impl<S: StrArg> Labels for Vec<S> {}
+impl Keys for () {}
+impl Keys for &str {}
+impl Keys for String {}
+impl Labels for &str {}
+impl Labels for String {}
+
+macro_rules! keys_impl {
+    ($($S:ident),+) => {
+        impl<$($S: StrArg),+> Keys for ($($S,)+) {}
+        impl<$($S: StrArg),+> Labels for ($($S,)+) {}
+        impl<$($S: StrArg),+> KeysOrPredicate for ($($S,)+) {}
+        impl<$($S: StrArg),+> DedupArgs for ($($S,)+) {}
+        impl<$($S: StrArg),+> DedupArgs for (Scope, $($S,)+) {}
+        impl<$($S: StrArg),+> SelectArgs for (Pop, $($S,)+) {}
+        impl<$($S: StrArg),+> ValueMapArgs for ($($S,)+) {}
+        impl<$($S: StrArg),+> ValueMapArgs for (bool, $($S,)+) {}
+    };
+}
+keys_impl![S0];
+keys_impl![S0, S1];
+keys_impl![S0, S1, S2];
+keys_impl![S0, S1, S2, S3];
+keys_impl![S0, S1, S2, S3, S4];
+keys_impl![S0, S1, S2, S3, S4, S5];
+keys_impl![S0, S1, S2, S3, S4, S5, S6];
+keys_impl![S0, S1, S2, S3, S4, S5, S6, S7];
+keys_impl![S0, S1, S2, S3, S4, S5, S6, S7, S8];
+keys_impl![S0, S1, S2, S3, S4, S5, S6, S7, S8, S9];
+
+// and, or, coalesce, union, match
+pub trait Traversals: Into<BytecodeStep> {}
+
+impl Traversals for () {}
+impl<S, E> Traversals for Vec<Traversal<S, E>> {}
+
+macro_rules! traversals_impl {
+    ($(($S:ident, $E:ident)),+) => {
+        impl<$($S, $E),+> Traversals for ($(Traversal<$S, $E>,)+) {}
+    };
+}
+traversals_impl![(S0, E0)];
+traversals_impl![(S0, E0), (S1, E1)];
+traversals_impl![(S0, E0), (S1, E1), (S2, E2)];
+traversals_impl![(S0, E0), (S1, E1), (S2, E2), (S3, E3)];
+traversals_impl![(S0, E0), (S1, E1), (S2, E2), (S3, E3), (S4, E4)];
+traversals_impl![(S0, E0), (S1, E1), (S2, E2), (S3, E3), (S4, E4), (S5, E5)];
+traversals_impl![
+    (S0, E0),
+    (S1, E1),
+    (S2, E2),
+    (S3, E3),
+    (S4, E4),
+    (S5, E5),
+    (S6, E6)
+];
+traversals_impl![
+    (S0, E0),
+    (S1, E1),
+    (S2, E2),
+    (S3, E3),
+    (S4, E4),
+    (S5, E5),
+    (S6, E6),
+    (S7, E7)
+];
+
+// steps accepting a single anonymous traversal of any start and end type
+macro_rules! traversal_impl {
+    ($($Trait:ident),+) => {
+        $(impl<S, E> $Trait for Traversal<S, E> {})+
+    };
+}
+traversal_impl!(
+    Traversals,
+    OptionalLabel,
+    Label,
+    ByArgs,
+    ChooseArgs,
+    EmitArgs,
+    Condition,
+    FromArgs,
+    OptionArgs,
+    RepeatArgs,
+    SelectArgs,
+    WhereArgs
+);
+
+// hasLabel, hasKey
+pub trait KeysOrPredicate: Into<BytecodeStep> {}
+
+impl<S: StrArg> KeysOrPredicate for Vec<S> {}
+marker_impl!(KeysOrPredicate: &str, String, P, TextP);
+
+// addV
+pub trait OptionalLabel: Into<BytecodeStep> {}
+
+marker_impl!(OptionalLabel: (), &str, String);
+
+// addE
+pub trait Label: Into<BytecodeStep> {}
+
+marker_impl!(Label: &str, String);
+
+// group, groupCount, tree
+pub trait OptionalKey: Into<BytecodeStep> {}
+
+marker_impl!(OptionalKey: (), &str, String);
+
+// aggregate
+pub trait SideEffectKey: Into<BytecodeStep> {}
+
+marker_impl!(SideEffectKey: &str, String);
+impl<S: StrArg> SideEffectKey for (Scope, S) {}
+
+pub trait BarrierArgs: Into<BytecodeStep> {}
+
+marker_impl!(BarrierArgs: (), i32, Barrier);
+
+pub trait ByArgs: Into<BytecodeStep> {}
+
+marker_impl!(ByArgs: (), &str, String, T, Order, Column);
+impl<S: StrArg> ByArgs for (S, Order) {}
+impl<S, E> ByArgs for (Traversal<S, E>, Order) {}
+impl ByArgs for (T, Order) {}
+impl ByArgs for (Column, Order) {}
+
+pub trait CallArgs: Into<BytecodeStep> {}
+
+marker_impl!(CallArgs: &str, String);
+impl<S: StrArg, V: Into<GsonV2>> CallArgs for (S, V) {}
+
+pub trait ChooseArgs: Into<BytecodeStep> {}
+
+impl<S0, E0, S1, E1> ChooseArgs for (Traversal<S0, E0>, Traversal<S1, E1>) {}
+impl<S0, E0, S1, E1, S2, E2> ChooseArgs
+    for (Traversal<S0, E0>, Traversal<S1, E1>, Traversal<S2, E2>)
+{
+}
+impl<S, E> ChooseArgs for (P, Traversal<S, E>) {}
+impl<S0, E0, S1, E1> ChooseArgs for (P, Traversal<S0, E0>, Traversal<S1, E1>) {}
+
+pub trait DedupArgs: Into<BytecodeStep> {}
+
+impl<S: StrArg> DedupArgs for Vec<S> {}
+marker_impl!(DedupArgs: (), &str, String, Scope);
+impl DedupArgs for (Scope,) {}
+
+// emit
+pub trait EmitArgs: Into<BytecodeStep> {}
+
+marker_impl!(EmitArgs: (), P);
+
+// until
+pub trait Condition: Into<BytecodeStep> {}
+
+impl Condition for P {}
+
+// from, to
+pub trait FromArgs: Into<BytecodeStep> {}
+
+marker_impl!(FromArgs: &str, String, Vertex);
+
+pub trait HasArgs: Into<BytecodeStep> {}
+
+marker_impl!(HasArgs: &str, String);
+impl<S: StrArg, V: Into<GsonV2>> HasArgs for (S, V) {}
+impl<V: Into<GsonV2>> HasArgs for (T, V) {}
+impl<L: StrArg, S: StrArg, V: Into<GsonV2>> HasArgs for (L, S, V) {}
+
+// limit, skip
+pub trait LimitArgs: Into<BytecodeStep> {}
+
+impl LimitArgs for i64 {}
+impl LimitArgs for (Scope, i64) {}
+
+pub trait RangeArgs: Into<BytecodeStep> {}
+
+impl RangeArgs for (i64, i64) {}
+impl RangeArgs for (Scope, i64, i64) {}
+
+pub trait SampleArgs: Into<BytecodeStep> {}
+
+impl SampleArgs for i32 {}
+impl SampleArgs for (Scope, i32) {}
+
+pub trait TailArgs: Into<BytecodeStep> {}
+
+marker_impl!(TailArgs: (), i64, Scope);
+impl TailArgs for (Scope, i64) {}
+
+// mergeV, mergeE
+pub trait MergeArgs: Into<BytecodeStep> {}
+
+impl MergeArgs for () {}
+impl<V: Into<GsonV2> + Debug + Clone> MergeArgs for V {}
+
+pub trait OptionArgs: Into<BytecodeStep> {}
+
+impl<K: Into<GsonV2>, V: Into<GsonV2>> OptionArgs for (K, V) {}
+
+pub trait PropertyArgs: Into<BytecodeStep> {}
+
+impl<S: StrArg, V: Into<GsonV2>> PropertyArgs for (S, V) {}
+impl<V: Into<GsonV2>> PropertyArgs for (T, V) {}
+impl<S: StrArg, V: Into<GsonV2>> PropertyArgs for (Cardinality, S, V) {}
+impl<S: StrArg, V: Into<GsonV2>, K: StrArg, W: Into<GsonV2>> PropertyArgs for (S, V, K, W) {}
+impl<S: StrArg, V: Into<GsonV2>, K: StrArg, W: Into<GsonV2>> PropertyArgs
+    for (Cardinality, S, V, K, W)
+{
+}
+
+pub trait RepeatArgs: Into<BytecodeStep> {}
+
+impl<K: StrArg, S, E> RepeatArgs for (K, Traversal<S, E>) {}
+
+pub trait SackArgs: Into<BytecodeStep> {}
+
+marker_impl!(SackArgs: (), Operator);
+impl<S: StrArg> SackArgs for (Operator, S) {}
+
+pub trait SelectArgs: Into<BytecodeStep> {}
+
+marker_impl!(SelectArgs: &str, String, Column);
+impl<S0: StrArg, S1: StrArg> SelectArgs for (S0, S1) {}
+impl<S0: StrArg, S1: StrArg, S2: StrArg> SelectArgs for (S0, S1, S2) {}
+impl<S0: StrArg, S1: StrArg, S2: StrArg, S3: StrArg> SelectArgs for (S0, S1, S2, S3) {}
+impl<S, E> SelectArgs for (Pop, Traversal<S, E>) {}
+
+pub trait ValueMapArgs: Into<BytecodeStep> {}
+
+impl<S: StrArg> ValueMapArgs for Vec<S> {}
+marker_impl!(ValueMapArgs: (), bool, &str, String);
+
+pub trait WhereArgs: Into<BytecodeStep> {}
+
+impl WhereArgs for P {}
+impl<S: StrArg> WhereArgs for (S, P) {}
+
+pub trait WithArgs: Into<BytecodeStep> {}
+
+marker_impl!(WithArgs: &str, String, WithOptions);
+impl<S: StrArg, V: Into<GsonV2>> WithArgs for (S, V) {}
+impl<V: Into<GsonV2>> WithArgs for (WithOptions, V) {}
+
+// terminal steps, () executes with the remote bound by TraversalSource::with_remote
+pub trait RemoteArg: Send {
+    fn executor(&self) -> Option<&dyn GremlinExecutor>;
+}
+
+impl RemoteArg for () {
+    fn executor(&self) -> Option<&dyn GremlinExecutor> {
+        None
+    }
+}
+
+impl<X: GremlinExecutor> RemoteArg for &X {
+    fn executor(&self) -> Option<&dyn GremlinExecutor> {
+        Some(*self)
+    }
+}
+
+impl RemoteArg for &dyn GremlinExecutor {
+    fn executor(&self) -> Option<&dyn GremlinExecutor> {
+        Some(*self)
+    }
+}
@@ -4,12 +4,28 @@ use std::fmt::Debug;
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Bytecode {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    source: Vec<Vec<GsonV2>>,
+    #[serde(default)]
     step: Vec<Vec<GsonV2>>,
 }
 
 impl Bytecode {
     pub fn new() -> Self {
-        Self { step: Vec::new() }
+        Self {
+            source: Vec::new(),
+            step: Vec::new(),
+        }
+    }
+
+    pub fn add_source<T: Into<BytecodeStep>>(&mut self, op: &str, args: T) {
+        let mut source: BytecodeStep = args.into();
+        source.0.insert(0, op.into());
+        self.source.push(source.0)
+    }
+
+    pub fn sources(&self) -> &[Vec<GsonV2>] {
+        &self.source
     }
 
     pub fn add_step<T: Into<BytecodeStep>>(&mut self, op: &str, args: T) {
@@ -22,11 +38,50 @@ impl Bytecode {
         self.step.push(vec![op.into()])
     }
 
+    // appends the other traversal's steps, its source instructions join ours unless already present
+    pub fn extend(&mut self, other: Bytecode) {
+        for source in other.source {
+            let value = serde_json::to_value(&source).ok();
+            if !self
+                .source
+                .iter()
+                .any(|s| serde_json::to_value(s).ok() == value)
+            {
+                self.source.push(source);
+            }
+        }
+        self.step.extend(other.step)
+    }
+
+    pub fn steps(&self) -> &[Vec<GsonV2>] {
+        &self.step
+    }
+
+    // the arguments of every source and traversal step
+    pub(crate) fn args_mut(&mut self) -> impl Iterator<Item = &mut GsonV2> {
+        self.source
+            .iter_mut()
+            .chain(self.step.iter_mut())
+            .flat_map(|step| step.iter_mut().skip(1))
+    }
+
     pub fn is_mutating(&self) -> bool {
-        todo!()
+        self.step.iter().any(|step| match step.split_first() {
+            Some((GsonV2::String(op), args)) => {
+                MUTATING_STEPS.contains(&op.as_str())
+                    || args
+                        .iter()
+                        .any(|a| matches!(a, GsonV2::Bytecode(b) if b.is_mutating()))
+            }
+            _ => false,
+        })
     }
 }
 
+const MUTATING_STEPS: [&str; 7] = [
+    "addE", "addV", "drop", "mergeE", "mergeV", "property", "write",
+];
+
 pub struct BytecodeStep(pub Vec<GsonV2>);
 
 impl<T: Into<GsonV2> + Debug + Clone> From<T> for BytecodeStep {
@@ -0,0 +1,148 @@
+use super::*;
+use crate::structure::{
+    de::{from_gson, GsonError},
+    Edge, Vertex,
+};
+
+use serde::{de, Deserialize, Deserializer};
+use std::collections::HashMap;
+
+// implemented by #[derive(GremlinVertex)], maps a struct to a vertex label and its properties
+pub trait GremlinVertex: Sized {
+    const LABEL: &'static str;
+
+    // None while the id is left to the server
+    fn vertex_id(&self) -> Option<GsonV2>;
+
+    // addV(label).property(...) with every mapped property
+    fn add_vertex(&self, source: &TraversalSource) -> Traversal<Vertex, Vertex>;
+
+    // property(single, ...) steps replacing every mapped property of the vertices `t` ends at
+    fn update_properties(&self, t: Traversal<Vertex, Vertex>) -> Traversal<Vertex, Vertex>;
+
+    // V(id).hasLabel(label) then update_properties, so a vertex of another label is left alone
+    fn update_vertex(&self, source: &TraversalSource) -> Traversal<Vertex, Vertex> {
+        let id = self.vertex_id().unwrap_or(GsonV2::Null);
+        self.update_properties(source.V(id).hasLabel(Self::LABEL))
+    }
+
+    // the vertex with this id and label, created when there is none, then update_properties,
+    // addV when the id is left to the server. the server rejects the creation when a vertex of
+    // another label has the id
+    fn upsert_vertex(&self, source: &TraversalSource) -> Traversal<Vertex, Vertex> {
+        match self.vertex_id() {
+            Some(id) => self.update_properties(source.upsert_v(Self::LABEL).id(id).traversal()),
+            None => self.add_vertex(source),
+        }
+    }
+
+    // a valueMap(true) or elementMap() result
+    fn from_map(map: HashMap<String, GsonV2>) -> Result<Self, GsonError>;
+}
+
+// implemented by #[derive(GremlinEdge)], maps a struct to an edge label, its endpoint vertex ids
+// and its properties
+pub trait GremlinEdge: Sized {
+    const LABEL: &'static str;
+
+    // None when the struct has no id field or the id is left to the server
+    fn edge_id(&self) -> Option<GsonV2>;
+
+    // addE(label).from(__.V(out)).to(__.V(in)).property(...) with every mapped property
+    fn add_edge(&self, source: &TraversalSource) -> Traversal<Edge, Edge>;
+
+    // V(out).outE(label) narrowed to this edge by id, or by its in vertex when there is no id,
+    // then property(...) replacing every mapped property
+    fn update_edge(&self, source: &TraversalSource) -> Traversal<Vertex, Edge>;
+
+    // an elementMap() result, the IN and OUT maps give the endpoint ids
+    fn from_map(map: HashMap<String, GsonV2>) -> Result<Self, GsonError>;
+}
+
+// takes `key` out of a valueMap(true) (values in lists) or elementMap() (single values) result,
+// multi properties are read as a list either way and missing ones as null
+pub fn property<T: de::DeserializeOwned>(
+    map: &mut HashMap<String, GsonV2>,
+    key: &str,
+    multi: bool,
+) -> Result<T, GsonError> {
+    let value = match (map.remove(key), multi) {
+        (Some(GsonV2::List(mut l)), false) if l.len() <= 1 => l.pop().unwrap_or(GsonV2::Null),
+        (None | Some(GsonV2::Null), true) => GsonV2::List(vec![]),
+        (Some(GsonV2::List(l)), true) => GsonV2::List(l),
+        (Some(v), true) => GsonV2::List(vec![v]),
+        (v, _) => v.unwrap_or(GsonV2::Null),
+    };
+    from_gson(&value).map_err(|e| GsonError::CustomError(format!("property {}: {}", key, e)))
+}
+
+// the id of the IN or OUT vertex map of an edge's elementMap() result
+pub fn endpoint<T: de::DeserializeOwned>(
+    map: &mut HashMap<String, GsonV2>,
+    direction: &str,
+) -> Result<T, GsonError> {
+    match map.remove(direction) {
+        Some(GsonV2::Map(mut vertex)) => property(&mut vertex, "id", false),
+        _ => Err(GsonError::CustomError(format!(
+            "missing {} vertex, expected an elementMap() result",
+            direction
+        ))),
+    }
+}
+
+// the Deserialize impls generated for mapped structs read the result as a map first
+pub fn deserialize_element<'de, D, T, F>(deserializer: D, from_map: F) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    F: FnOnce(HashMap<String, GsonV2>) -> Result<T, GsonError>,
+{
+    let map = HashMap::<String, GsonV2>::deserialize(deserializer)?;
+    from_map(map).map_err(de::Error::custom)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn map(json: &str) -> HashMap<String, GsonV2> {
+        crate::structure::de::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn element_properties() {
+        let value_map = r#"{"id":{"@type":"g:Int64","@value":1},"label":"person","name":["marko"],"age":[{"@type":"g:Int32","@value":29}],"tags":["a","b"]}"#;
+        let mut m = map(value_map);
+        assert_eq!(property::<i64>(&mut m, "id", false).unwrap(), 1);
+        assert_eq!(property::<String>(&mut m, "name", false).unwrap(), "marko");
+        assert_eq!(property::<i32>(&mut m, "age", false).unwrap(), 29);
+        assert_eq!(
+            property::<Vec<String>>(&mut m, "tags", true).unwrap(),
+            ["a", "b"]
+        );
+        assert_eq!(
+            property::<Option<String>>(&mut m, "nick", false).unwrap(),
+            None
+        );
+        assert_eq!(
+            property::<Vec<String>>(&mut m, "nick", true).unwrap().len(),
+            0
+        );
+
+        let element_map =
+            r#"{"id":{"@type":"g:Int64","@value":1},"label":"person","name":"marko","tags":"a"}"#;
+        let mut m = map(element_map);
+        assert_eq!(property::<String>(&mut m, "name", false).unwrap(), "marko");
+        assert_eq!(
+            property::<Vec<String>>(&mut m, "tags", true).unwrap(),
+            ["a"]
+        );
+        assert!(property::<String>(&mut m, "missing", false).is_err());
+
+        let edge_map = r#"{"id":{"@type":"g:Int32","@value":7},"label":"knows","IN":{"id":{"@type":"g:Int64","@value":2},"label":"person"},"OUT":{"id":{"@type":"g:Int64","@value":1},"label":"person"},"weight":{"@type":"g:Double","@value":0.5}}"#;
+        let mut m = map(edge_map);
+        assert_eq!(endpoint::<i64>(&mut m, "OUT").unwrap(), 1);
+        assert_eq!(endpoint::<i64>(&mut m, "IN").unwrap(), 2);
+        assert_eq!(property::<f64>(&mut m, "weight", false).unwrap(), 0.5);
+        assert!(endpoint::<i64>(&mut m, "IN").is_err());
+    }
+}
@@ -0,0 +1,382 @@
+use super::*;
+use crate::process::bytecode::{Bytecode, BytecodeStep};
+
+use gremlin_rs_grammar::{parse_query, Number, Step, Value};
+use serde::de::DeserializeOwned;
+use std::{collections::HashMap, str::FromStr};
+
+// lowers the syntax tree of gremlin_rs_grammar, the gremlin-groovy subset the gremlin! macro
+// also reads, to bytecode
+
+pub use gremlin_rs_grammar::ParseError;
+use ParseError::*;
+
+pub fn parse_traversal(query: &str) -> Result<Traversal, ParseError> {
+    let query = parse_query(query)?;
+    let mut bytecode = Bytecode::new();
+    for step in query.configuration {
+        bytecode.add_source(&step.op, BytecodeStep(values(step.args)?));
+    }
+    add_steps(&mut bytecode, query.steps)?;
+    let alias = query.source.unwrap_or_else(|| "g".to_string());
+    Ok(Traversal::from_source(alias, bytecode))
+}
+
+pub fn parse_bytecode(query: &str) -> Result<Bytecode, ParseError> {
+    parse_traversal(query).map(Into::into)
+}
+
+impl FromStr for Traversal {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_traversal(s)
+    }
+}
+
+fn enum_token<E: DeserializeOwned + Into<GsonV2>>(name: &str) -> Option<GsonV2> {
+    serde_json::from_value::<E>(serde_json::Value::String(name.to_string()))
+        .ok()
+        .map(Into::into)
+}
+
+// the enums serialize under their gremlin names so serde resolves the tokens
+fn token(class: &str, name: &str) -> Option<GsonV2> {
+    match class {
+        "T" => enum_token::<T>(name),
+        "Order" => enum_token::<Order>(name),
+        "Scope" => enum_token::<Scope>(name),
+        "Cardinality" => enum_token::<Cardinality>(name),
+        "Direction" => enum_token::<Direction>(name),
+        "Pop" => enum_token::<Pop>(name),
+        "Pick" => enum_token::<Pick>(name),
+        "Merge" => enum_token::<Merge>(name),
+        "Operator" => enum_token::<Operator>(name),
+        "Column" => enum_token::<Column>(name),
+        "Barrier" => enum_token::<Barrier>(name),
+        "DT" => enum_token::<DT>(name),
+        "WithOptions" => enum_token::<WithOptions>(name),
+        _ => None,
+    }
+}
+
+fn add_steps(bytecode: &mut Bytecode, steps: Vec<Step>) -> Result<(), ParseError> {
+    for step in steps {
+        bytecode.add_step(&step.op, BytecodeStep(values(step.args)?));
+    }
+    Ok(())
+}
+
+fn values(values: Vec<Value>) -> Result<Vec<GsonV2>, ParseError> {
+    values.into_iter().map(value).collect()
+}
+
+fn value(v: Value) -> Result<GsonV2, ParseError> {
+    Ok(match v {
+        Value::Str(s) => GsonV2::String(s),
+        Value::Number { value, .. } => match value {
+            Number::Integer(i) => GsonV2::Integer(i),
+            Number::Long(l) => GsonV2::Long(l),
+            Number::Float(f) => GsonV2::Float(f),
+            Number::Double(d) => GsonV2::Double(d),
+        },
+        Value::Bool(b) => GsonV2::Bool(b),
+        Value::Null => GsonV2::Null,
+        Value::Variable(_) => unreachable!("parse_query reads no variables"),
+        Value::List(l) => GsonV2::List(values(l)?),
+        Value::Map(entries) => {
+            let mut map = GsonMap::new();
+            for (k, v) in entries {
+                map = map.with(value(k)?, value(v)?);
+            }
+            string_keyed(map)
+        }
+        Value::Token(class, name) => {
+            token(class, name).expect("the grammar's tokens are the enums' serde names")
+        }
+        Value::Predicate {
+            pos,
+            class,
+            op,
+            args,
+            modifiers,
+        } => predicate(pos, class, &op, values(args)?, modifiers)?,
+        Value::Strategy(name, configuration) => {
+            let mut strategy = TraversalStrategy::new(name);
+            for (k, v) in configuration {
+                strategy.configuration.insert(k, value(v)?);
+            }
+            strategy.into()
+        }
+        Value::Traversal(steps) => {
+            let mut bytecode = Bytecode::new();
+            add_steps(&mut bytecode, steps)?;
+            bytecode.into()
+        }
+    })
+}
+
+fn predicate(
+    pos: usize,
+    class: &str,
+    op: &str,
+    mut args: Vec<GsonV2>,
+    modifiers: Vec<Step>,
+) -> Result<GsonV2, ParseError> {
+    let unknown = || Unknown(pos, "predicate", format!("{}.{}", class, op));
+    let mut p: GsonV2 = match (class, op, args.len()) {
+        ("P", "eq", 1) => P::eq(args.remove(0)).into(),
+        ("P", "neq", 1) => P::neq(args.remove(0)).into(),
+        ("P", "lt", 1) => P::lt(args.remove(0)).into(),
+        ("P", "lte", 1) => P::lte(args.remove(0)).into(),
+        ("P", "gt", 1) => P::gt(args.remove(0)).into(),
+        ("P", "gte", 1) => P::gte(args.remove(0)).into(),
+        ("P", "not", 1) => P::not(args.remove(0)).into(),
+        ("P", "inside" | "outside" | "between", 2) => {
+            let (a, b) = (args.remove(0), args.remove(0));
+            match op {
+                "inside" => P::inside(a, b),
+                "outside" => P::outside(a, b),
+                _ => P::between(a, b),
+            }
+            .into()
+        }
+        ("P", "within" | "without", _) => {
+            if let [GsonV2::List(_)] = args.as_slice() {
+                if let GsonV2::List(l) = args.remove(0) {
+                    args = l;
+                }
+            }
+            match op {
+                "within" => P::within(BytecodeStep(args)),
+                _ => P::without(BytecodeStep(args)),
+            }
+            .into()
+        }
+        ("TextP", _, 1) => match (op, args.remove(0)) {
+            ("startingWith", GsonV2::String(s)) => TextP::startingWith(s),
+            ("endingWith", GsonV2::String(s)) => TextP::endingWith(s),
+            ("containing", GsonV2::String(s)) => TextP::containing(s),
+            ("notStartingWith", GsonV2::String(s)) => TextP::notStartingWith(s),
+            ("notEndingWith", GsonV2::String(s)) => TextP::notEndingWith(s),
+            ("notContaining", GsonV2::String(s)) => TextP::notContaining(s),
+            ("regex", GsonV2::String(s)) => TextP::regex(s),
+            ("notRegex", GsonV2::String(s)) => TextP::notRegex(s),
+            _ => return Err(unknown()),
+        }
+        .into(),
+        _ => return Err(unknown()),
+    };
+
+    // and, or, negate chained onto the predicate
+    for step in modifiers {
+        let mut args = values(step.args)?;
+        p = match (step.op.as_str(), args.len(), p) {
+            ("negate", 0, p) => negate_gson(p),
+            ("and", 1, GsonV2::Predicate(p)) => p.and(args.remove(0)).into(),
+            ("and", 1, GsonV2::TextPredicate(p)) => p.and(args.remove(0)).into(),
+            ("or", 1, GsonV2::Predicate(p)) => p.or(args.remove(0)).into(),
+            ("or", 1, GsonV2::TextPredicate(p)) => p.or(args.remove(0)).into(),
+            _ => return Err(Unknown(step.pos, "predicate modifier", step.op)),
+        };
+    }
+    Ok(p)
+}
+
+// maps keyed only by strings stay plain GsonV2::Map values
+fn string_keyed(map: GsonMap) -> GsonV2 {
+    let mut strings = HashMap::with_capacity(map.len());
+    for (k, v) in map.0.iter() {
+        match k {
+            GsonV2::String(k) => strings.insert(k.clone(), v.clone()),
+            _ => return GsonV2::GMap(map),
+        };
+    }
+    GsonV2::Map(strings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{to_value, Value};
+
+    fn steps(query: &str) -> Value {
+        to_value(parse_bytecode(query).unwrap()).unwrap()
+    }
+
+    fn built<S, E>(t: Traversal<S, E>) -> Value {
+        let b: Bytecode = t.into();
+        to_value(b).unwrap()
+    }
+
+    #[test]
+    fn parse_steps() {
+        assert_eq!(
+            steps("g.V().has('person','name','marko').out('knows')"),
+            built(g.V(()).has(("person", "name", "marko")).out("knows"))
+        );
+        assert_eq!(
+            steps(
+                r#"g.V().hasLabel("person")
+                    .where(__.out('created').count().is(P.gt(1)))
+                    .order().by('age', desc)
+                    .values('name').limit(2L).toList()"#
+            ),
+            built(
+                g.V(())
+                    .hasLabel("person")
+                    .where_(__.out("created").count().is(P::gt(1)))
+                    .order()
+                    .by(("age", Order::Desc))
+                    .values("name")
+                    .limit(2i64)
+            )
+        );
+        assert_eq!(
+            steps("g.V().repeat(out()).times(2).emit().path().by(T.label)"),
+            built(
+                g.V(())
+                    .repeat(__.out(()))
+                    .times(2)
+                    .emit(())
+                    .path()
+                    .by(T::Label)
+            )
+        );
+        assert_eq!(
+            parse_traversal("__.out('knows').count()").unwrap().alias(),
+            "g"
+        );
+        assert_eq!(
+            parse_traversal("gmodern.V(1L).values('name');")
+                .unwrap()
+                .alias(),
+            "gmodern"
+        );
+    }
+
+    #[test]
+    fn parse_arguments() {
+        assert_eq!(
+            steps("g.V().has('age', within(1, 2)).has('name', P.within(['a', 'b']))"),
+            built(
+                g.V(())
+                    .has(("age", P::within((1, 2))))
+                    .has(("name", P::within(("a", "b"))))
+            )
+        );
+        assert_eq!(
+            steps("g.V().has('name', TextP.containing('ar').or(startingWith('m')).negate())"),
+            built(
+                g.V(()).has((
+                    "name",
+                    TextP::containing("ar")
+                        .or(TextP::startingWith("m"))
+                        .negate()
+                ))
+            )
+        );
+        assert_eq!(
+            steps("g.V('a').property(single, 'age', 30).property(list, 'x', -1.5d)"),
+            built(
+                g.V("a")
+                    .property((Cardinality::Single, "age", 30))
+                    .property((Cardinality::List, "x", -1.5))
+            )
+        );
+        assert_eq!(
+            steps("g.V().select(Pop.first, 'a').local(__.limit(Scope.local, 3L)).toE(OUT)"),
+            built(
+                g.V(())
+                    .select((Pop::First, "a"))
+                    .local(__.limit((Scope::Local, 3i64)))
+                    .toE(Direction::Out, ())
+            )
+        );
+
+        assert_eq!(
+            steps(
+                "g.withSack(1.0d, Operator.sum).V().sack(mult).by('weight').barrier(normSack)\
+                .group().by(label).select(keys).valueMap().with(WithOptions.tokens, WithOptions.all)"
+            ),
+            built(
+                g.withSack((1.0, Operator::Sum))
+                    .V(())
+                    .sack(Operator::Mult)
+                    .by("weight")
+                    .barrier(Barrier::NormSack)
+                    .group(())
+                    .by(T::Label)
+                    .select(Column::Keys)
+                    .valueMap(())
+                    .with((WithOptions::Tokens, WithOptions::All))
+            )
+        );
+
+        let v = steps("g.withSideEffect('x', [a: 1, 'b': [true, null, 2147483648]]).inject([:])");
+        assert_eq!(v["source"][0][2]["b"][2]["@type"], "g:Int64");
+        assert_eq!(v["source"][0][2]["a"]["@type"], "g:Int32");
+        assert_eq!(v["step"][0][1], serde_json::json!({}));
+
+        assert_eq!(
+            steps("g.mergeE([(T.label): 'knows', (Direction.OUT): 1L, weight: 0.5d])"),
+            built(
+                g.mergeE(
+                    GsonMap::new()
+                        .with(T::Label, "knows")
+                        .with(Direction::Out, 1i64)
+                        .with("weight", 0.5)
+                )
+            )
+        );
+    }
+
+    #[test]
+    fn parse_strategies() {
+        assert_eq!(
+            steps("g.withStrategies(new SeedStrategy(seed: 99L)).withoutStrategies(ReadOnlyStrategy).V()"),
+            built(
+                g.withStrategies(SeedStrategy { seed: 99 })
+                    .withoutStrategies(TraversalStrategy::new("ReadOnlyStrategy"))
+                    .V(())
+            )
+        );
+        let v =
+            steps("g.withStrategies(new SubgraphStrategy(vertices: __.hasLabel('person'))).V()");
+        assert_eq!(v["source"][0][1]["@type"], "g:SubgraphStrategy");
+        assert_eq!(
+            v["source"][0][1]["@value"]["vertices"]["@type"],
+            "g:Bytecode"
+        );
+    }
+
+    #[test]
+    fn grammar_tokens() {
+        for (class, names) in gremlin_rs_grammar::TOKENS {
+            for name in names {
+                assert!(token(class, name).is_some(), "{}.{}", class, name);
+            }
+        }
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(parse_bytecode("g.V().out(").unwrap_err(), Eof("argument"));
+        assert_eq!(parse_bytecode("g").unwrap_err(), Eof("."));
+        assert_eq!(
+            parse_bytecode("g.V().has('a', bogus)").unwrap_err(),
+            Unknown(15, "token", "bogus".to_string())
+        );
+        assert_eq!(
+            parse_bytecode("g.V().count() x").unwrap_err(),
+            InvalidSyntax(14, "end of query", "x".to_string())
+        );
+        assert!(matches!(
+            parse_bytecode("g.V().has('a', P.like(1))"),
+            Err(Unknown(17, "predicate", _))
+        ));
+        assert!("g.V().count().toList().next()"
+            .parse::<Traversal>()
+            .is_err());
+    }
+}
@@ -0,0 +1,289 @@
+use super::*;
+use crate::{driver::ClientError, structure::Vertex};
+
+use serde::de::DeserializeOwned;
+use std::marker::PhantomData;
+
+// typed access to the vertices of a GremlinVertex type, the traversals run on `remote` (a
+// ClientPool sends the mutating ones to its writers) or, given (), on the source's bound remote.
+// reads use valueMap(true) so list and set properties come back whole
+pub struct Repository<T, R = ()> {
+    source: TraversalSource,
+    remote: R,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T, R: Clone> Clone for Repository<T, R> {
+    fn clone(&self) -> Self {
+        Self {
+            source: self.source.clone(),
+            remote: self.remote.clone(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T, R> Repository<T, R>
+where
+    T: GremlinVertex + DeserializeOwned,
+    R: RemoteArg + Copy,
+{
+    pub fn new(source: &TraversalSource, remote: R) -> Self {
+        Self {
+            source: source.clone(),
+            remote,
+            marker: PhantomData,
+        }
+    }
+
+    pub fn source(&self) -> &TraversalSource {
+        &self.source
+    }
+
+    fn vertices<I: Into<GsonV2>>(&self, id: I) -> Traversal<Vertex, Vertex> {
+        self.source.V(id.into()).hasLabel(T::LABEL)
+    }
+
+    pub async fn get_by_id<I: Into<GsonV2>>(&self, id: I) -> Result<Option<T>, ClientError> {
+        self.vertices(id)
+            .valueMap(true)
+            .cast::<T>()
+            .next(self.remote)
+            .await
+    }
+
+    pub async fn find_by<V: Into<GsonV2>>(
+        &self,
+        property: &str,
+        value: V,
+    ) -> Result<Vec<T>, ClientError> {
+        self.source
+            .V(())
+            .hasLabel(T::LABEL)
+            .has((property, value))
+            .valueMap(true)
+            .cast::<T>()
+            .to_list(self.remote)
+            .await
+    }
+
+    // the stored vertex, with its id when the server assigned one
+    pub async fn insert(&self, entity: &T) -> Result<T, ClientError> {
+        entity
+            .add_vertex(&self.source)
+            .valueMap(true)
+            .cast::<T>()
+            .one(self.remote)
+            .await
+    }
+
+    // None when no vertex of T's label has the entity's id
+    pub async fn update(&self, entity: &T) -> Result<Option<T>, ClientError> {
+        entity
+            .update_vertex(&self.source)
+            .valueMap(true)
+            .cast::<T>()
+            .next(self.remote)
+            .await
+    }
+
+    // an update, or an insert when no vertex of T's label has the entity's id, as one traversal.
+    // ids are unique across labels, so when the id belongs to a vertex of another label the
+    // insert is rejected and this returns the server's ClientError::ResponseError
+    pub async fn upsert(&self, entity: &T) -> Result<T, ClientError> {
+        entity
+            .upsert_vertex(&self.source)
+            .valueMap(true)
+            .cast::<T>()
+            .one(self.remote)
+            .await
+    }
+
+    pub async fn delete<I: Into<GsonV2>>(&self, id: I) -> Result<(), ClientError> {
+        self.vertices(id).drop().iterate(self.remote).await
+    }
+
+    // the N vertices adjacent to vertex `id` through `edge_label` edges
+    pub async fn neighbors<N, I>(
+        &self,
+        id: I,
+        edge_label: &str,
+        direction: Direction,
+    ) -> Result<Vec<N>, ClientError>
+    where
+        N: GremlinVertex + DeserializeOwned,
+        I: Into<GsonV2>,
+    {
+        let start = self.vertices(id);
+        let adjacent = match direction {
+            Direction::Out => start.out(edge_label),
+            Direction::In => start.in_(edge_label),
+            Direction::Both => start.both(edge_label),
+        };
+        adjacent
+            .hasLabel(N::LABEL)
+            .valueMap(true)
+            .cast::<N>()
+            .to_list(self.remote)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        driver::{ClientResponse, GremlinExecutor},
+        process::bytecode::Bytecode,
+        structure::de::GsonError,
+    };
+    use futures::future::BoxFuture;
+    use serde::{Deserialize, Deserializer};
+    use std::{collections::HashMap, sync::Mutex};
+
+    #[derive(Debug, PartialEq)]
+    struct Person {
+        id: Option<i64>,
+        name: String,
+    }
+
+    impl GremlinVertex for Person {
+        const LABEL: &'static str = "person";
+
+        fn vertex_id(&self) -> Option<GsonV2> {
+            self.id.map(Into::into)
+        }
+
+        fn add_vertex(&self, source: &TraversalSource) -> Traversal<Vertex, Vertex> {
+            source
+                .addV(Self::LABEL)
+                .property(("name", self.name.as_str()))
+        }
+
+        fn update_properties(&self, t: Traversal<Vertex, Vertex>) -> Traversal<Vertex, Vertex> {
+            t.property((Cardinality::Single, "name", self.name.as_str()))
+        }
+
+        fn from_map(mut map: HashMap<String, GsonV2>) -> Result<Self, GsonError> {
+            Ok(Self {
+                id: property(&mut map, "id", false)?,
+                name: property(&mut map, "name", false)?,
+            })
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Person {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            deserialize_element(deserializer, Self::from_map)
+        }
+    }
+
+    // records each query and replies with the queued frames' data, in order
+    struct Recorder {
+        queries: Mutex<Vec<Bytecode>>,
+        data: Mutex<Vec<&'static str>>,
+    }
+
+    impl GremlinExecutor for Recorder {
+        fn execute(&self, query: Traversal) -> BoxFuture<'_, Result<ClientResponse, ClientError>> {
+            self.queries.lock().unwrap().push(query.into());
+            let data = self.data.lock().unwrap().remove(0);
+            let frame = format!(
+                r#"{{"requestId":"b65e6f64-a839-4c3f-a33b-047d9798f94a","status":{{"message":"","code":200,"attributes":{{}}}},"result":{{"data":{},"meta":{{}}}}}}"#,
+                data
+            );
+            Box::pin(async { Ok(ClientResponse(vec![frame.into_bytes()])) })
+        }
+    }
+
+    fn steps(query: &Bytecode) -> serde_json::Value {
+        serde_json::to_value(query).unwrap()
+    }
+
+    const MARKO: &str =
+        r#"[{"id":{"@type":"g:Int64","@value":1},"label":"person","name":["marko"]}]"#;
+
+    #[tokio::test]
+    async fn typed_crud() {
+        let recorder = Recorder {
+            queries: Mutex::new(vec![]),
+            data: Mutex::new(vec![MARKO, MARKO, MARKO, "[]", MARKO, "[]", MARKO]),
+        };
+        let people: Repository<Person, _> = Repository::new(&g, &recorder);
+        let marko = Person {
+            id: Some(1),
+            name: "marko".to_string(),
+        };
+
+        assert_eq!(people.get_by_id(1i64).await.unwrap(), Some(marko));
+        assert_eq!(people.find_by("name", "marko").await.unwrap().len(), 1);
+        let inserted = people
+            .insert(&Person {
+                id: None,
+                name: "marko".to_string(),
+            })
+            .await
+            .unwrap();
+        assert_eq!(inserted.id, Some(1));
+
+        // the empty reply: no person has id 1, so the update writes nothing and the upsert adds
+        // it (the server would reject that addV if a vertex of another label had id 1)
+        let renamed = Person {
+            id: Some(1),
+            name: "marko".to_string(),
+        };
+        assert_eq!(people.update(&renamed).await.unwrap(), None);
+        let upserted = people.upsert(&renamed).await.unwrap();
+        assert_eq!(upserted.name, "marko");
+        people.delete(1i64).await.unwrap();
+        let friends: Vec<Person> = people
+            .neighbors(1i64, "knows", Direction::Out)
+            .await
+            .unwrap();
+        assert_eq!(friends.len(), 1);
+
+        let queries = recorder.queries.lock().unwrap();
+        let expected = [
+            g.V(1i64)
+                .hasLabel("person")
+                .valueMap(true)
+                .limit(1i64)
+                .untyped(),
+            g.V(())
+                .hasLabel("person")
+                .has(("name", "marko"))
+                .valueMap(true)
+                .untyped(),
+            g.addV("person")
+                .property(("name", "marko"))
+                .valueMap(true)
+                .limit(2i64)
+                .untyped(),
+            g.V(1i64)
+                .hasLabel("person")
+                .property((Cardinality::Single, "name", "marko"))
+                .valueMap(true)
+                .limit(1i64)
+                .untyped(),
+            g.V(1i64)
+                .hasLabel("person")
+                .fold()
+                .coalesce((__.unfold(), __.addV("person").property((T::Id, 1i64))))
+                .property((Cardinality::Single, "name", "marko"))
+                .valueMap(true)
+                .limit(2i64)
+                .untyped(),
+            g.V(1i64).hasLabel("person").drop().none().untyped(),
+            g.V(1i64)
+                .hasLabel("person")
+                .out("knows")
+                .hasLabel("person")
+                .valueMap(true)
+                .untyped(),
+        ];
+        assert_eq!(queries.len(), expected.len());
+        for (query, t) in queries.iter().zip(expected) {
+            assert_eq!(steps(query), steps(t.bytecode()), "{}", t);
+        }
+    }
+}
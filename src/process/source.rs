@@ -1,34 +1,159 @@
 use super::*;
-use crate::process::bytecode::BytecodeStep;
+use crate::{
+    driver::GremlinExecutor,
+    process::bytecode::{Bytecode, BytecodeStep},
+    structure::{Edge, Vertex},
+};
+use std::{fmt, sync::Arc};
 
+#[derive(Clone)]
 pub struct TraversalSource {
-    traversal: Traversal,
+    alias: String,
+    bytecode: Bytecode,
+    remote: Option<Arc<dyn GremlinExecutor>>,
+    capability: ServerCapability,
+}
+
+impl fmt::Debug for TraversalSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TraversalSource")
+            .field("alias", &self.alias)
+            .field("bytecode", &self.bytecode)
+            .field("remote", &self.remote.is_some())
+            .field("capability", &self.capability)
+            .finish()
+    }
+}
+
+impl Default for TraversalSource {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl TraversalSource {
     pub fn new() -> Self {
+        Self::with_alias("g")
+    }
+
+    pub fn with_alias<A: Into<String>>(alias: A) -> Self {
         Self {
-            traversal: Traversal::new(),
+            alias: alias.into(),
+            bytecode: Bytecode::new(),
+            remote: None,
+            capability: ServerCapability::default(),
         }
     }
 
-    pub fn V<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Traversal {
-        self.traversal.clone().V(args)
+    // traversals spawned from the returned source run on `remote` when a terminal step is
+    // passed () instead of a client
+    pub fn with_remote<X: GremlinExecutor + 'static>(&self, remote: X) -> Self {
+        let mut source = self.clone();
+        source.remote = Some(Arc::new(remote));
+        source
+    }
+
+    // the steps the server supports, deciding the form of the upsert_v / upsert_e traversals
+    pub fn with_capability(&self, capability: ServerCapability) -> Self {
+        let mut source = self.clone();
+        source.capability = capability;
+        source
+    }
+
+    pub fn capability(&self) -> ServerCapability {
+        self.capability
+    }
+
+    pub fn is_bound(&self) -> bool {
+        self.remote.is_some()
+    }
+
+    pub fn alias(&self) -> &str {
+        &self.alias
+    }
+
+    pub fn bytecode(&self) -> &Bytecode {
+        &self.bytecode
+    }
+
+    fn spawn<S, E>(&self) -> Traversal<S, E> {
+        Traversal::from_source(self.alias.as_str(), self.bytecode.clone()).bind(self.remote.clone())
+    }
+
+    fn configure<T: Into<BytecodeStep>>(&self, op: &str, args: T) -> Self {
+        let mut source = self.clone();
+        source.bytecode.add_source(op, args);
+        source
+    }
+
+    pub fn withBulk<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Self {
+        self.configure("withBulk", args)
+    }
+
+    pub fn withComputer<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Self {
+        self.configure("withComputer", args)
+    }
+
+    pub fn withPath(&self) -> Self {
+        self.configure("withPath", ())
+    }
+
+    pub fn withSack<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Self {
+        self.configure("withSack", args)
+    }
+
+    pub fn withSideEffect<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Self {
+        self.configure("withSideEffect", args)
+    }
+
+    pub fn withStrategies<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Self {
+        self.configure("withStrategies", args)
+    }
+
+    pub fn withoutStrategies<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Self {
+        self.configure("withoutStrategies", args)
+    }
+
+    pub fn V<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Traversal<Vertex, Vertex> {
+        self.spawn::<Vertex, Vertex>().V(args)
+    }
+
+    pub fn addE<A: Label>(&self, args: A) -> Traversal<Edge, Edge> {
+        self.spawn::<Edge, Edge>().addE(args)
+    }
+
+    pub fn addV<A: OptionalLabel>(&self, args: A) -> Traversal<Vertex, Vertex> {
+        self.spawn::<Vertex, Vertex>().addV(args)
+    }
+
+    pub fn E<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Traversal<Edge, Edge> {
+        self.spawn::<Edge, Edge>().E(args)
+    }
+
+    pub fn mergeE<A: MergeArgs>(&self, args: A) -> Traversal<Edge, Edge> {
+        self.spawn::<Edge, Edge>().mergeE(args)
     }
 
-    pub fn addE<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Traversal {
-        self.traversal.clone().addE(args)
+    pub fn mergeV<A: MergeArgs>(&self, args: A) -> Traversal<Vertex, Vertex> {
+        self.spawn::<Vertex, Vertex>().mergeV(args)
     }
 
-    pub fn addV<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Traversal {
-        self.traversal.clone().addV(args)
+    // an idempotent write of the `label` vertex matching the key properties
+    pub fn upsert_v<L: Into<String>>(&self, label: L) -> VertexUpsert {
+        VertexUpsert::new(self, label)
     }
 
-    pub fn E<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Traversal {
-        self.traversal.clone().E(args)
+    // an idempotent write of the `label` edge from vertex `out_v` to vertex `in_v`
+    pub fn upsert_e<L, O, I>(&self, label: L, out_v: O, in_v: I) -> EdgeUpsert
+    where
+        L: Into<String>,
+        O: Into<GsonV2>,
+        I: Into<GsonV2>,
+    {
+        EdgeUpsert::new(self, label, out_v, in_v)
     }
 
     pub fn inject<T: Into<BytecodeStep> + Clone>(&self, args: T) -> Traversal {
-        self.traversal.clone().inject(args)
+        self.spawn::<GsonV2, GsonV2>().inject(args)
     }
 }
@@ -0,0 +1,178 @@
+use super::*;
+use std::collections::HashMap;
+
+#[derive(Debug, Clone)]
+pub struct TraversalStrategy {
+    pub name: String,
+    pub configuration: HashMap<String, GsonV2>,
+}
+
+impl TraversalStrategy {
+    pub fn new<N: Into<String>>(name: N) -> Self {
+        Self {
+            name: name.into(),
+            configuration: HashMap::new(),
+        }
+    }
+
+    fn config<V: Into<GsonV2>>(mut self, key: &str, value: Option<V>) -> Self {
+        if let Some(v) = value {
+            self.configuration.insert(key.to_string(), v.into());
+        }
+        self
+    }
+}
+
+fn strings(v: Vec<String>) -> Option<GsonV2> {
+    if v.is_empty() {
+        None
+    } else {
+        Some(GsonV2::List(v.into_iter().map(GsonV2::String).collect()))
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct PartitionStrategy {
+    pub partition_key: Option<String>,
+    pub write_partition: Option<String>,
+    pub read_partitions: Vec<String>,
+    pub include_meta_properties: Option<bool>,
+}
+
+impl From<PartitionStrategy> for TraversalStrategy {
+    fn from(s: PartitionStrategy) -> Self {
+        TraversalStrategy::new("PartitionStrategy")
+            .config("partitionKey", s.partition_key)
+            .config("writePartition", s.write_partition)
+            .config("readPartitions", strings(s.read_partitions))
+            .config("includeMetaProperties", s.include_meta_properties)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SubgraphStrategy {
+    pub vertices: Option<Traversal>,
+    pub edges: Option<Traversal>,
+    pub vertex_properties: Option<Traversal>,
+    pub check_adjacent_vertices: Option<bool>,
+}
+
+impl From<SubgraphStrategy> for TraversalStrategy {
+    fn from(s: SubgraphStrategy) -> Self {
+        TraversalStrategy::new("SubgraphStrategy")
+            .config("vertices", s.vertices)
+            .config("edges", s.edges)
+            .config("vertexProperties", s.vertex_properties)
+            .config("checkAdjacentVertices", s.check_adjacent_vertices)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ReadOnlyStrategy;
+
+impl From<ReadOnlyStrategy> for TraversalStrategy {
+    fn from(_: ReadOnlyStrategy) -> Self {
+        TraversalStrategy::new("ReadOnlyStrategy")
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct OptionsStrategy {
+    pub options: HashMap<String, GsonV2>,
+}
+
+impl From<OptionsStrategy> for TraversalStrategy {
+    fn from(s: OptionsStrategy) -> Self {
+        let mut strategy = TraversalStrategy::new("OptionsStrategy");
+        strategy.configuration = s.options;
+        strategy
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SeedStrategy {
+    pub seed: i64,
+}
+
+impl From<SeedStrategy> for TraversalStrategy {
+    fn from(s: SeedStrategy) -> Self {
+        TraversalStrategy::new("SeedStrategy").config("seed", Some(s.seed))
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct EdgeLabelVerificationStrategy {
+    pub log_warning: Option<bool>,
+    pub throw_exception: Option<bool>,
+}
+
+impl From<EdgeLabelVerificationStrategy> for TraversalStrategy {
+    fn from(s: EdgeLabelVerificationStrategy) -> Self {
+        TraversalStrategy::new("EdgeLabelVerificationStrategy")
+            .config("logWarning", s.log_warning)
+            .config("throwException", s.throw_exception)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ReservedKeysVerificationStrategy {
+    pub log_warning: Option<bool>,
+    pub throw_exception: Option<bool>,
+    pub keys: Vec<String>,
+}
+
+impl From<ReservedKeysVerificationStrategy> for TraversalStrategy {
+    fn from(s: ReservedKeysVerificationStrategy) -> Self {
+        TraversalStrategy::new("ReservedKeysVerificationStrategy")
+            .config("logWarning", s.log_warning)
+            .config("throwException", s.throw_exception)
+            .config("keys", strings(s.keys))
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ElementIdStrategy {
+    pub id_property_key: Option<String>,
+}
+
+impl From<ElementIdStrategy> for TraversalStrategy {
+    fn from(s: ElementIdStrategy) -> Self {
+        TraversalStrategy::new("ElementIdStrategy").config("idPropertyKey", s.id_property_key)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct HaltedTraverserStrategy {
+    pub halted_traverser_factory: Option<String>,
+}
+
+impl From<HaltedTraverserStrategy> for TraversalStrategy {
+    fn from(s: HaltedTraverserStrategy) -> Self {
+        TraversalStrategy::new("HaltedTraverserStrategy")
+            .config("haltedTraverserFactory", s.halted_traverser_factory)
+    }
+}
+
+macro_rules! strategy_gson_impl {
+    ($($S:ident),+) => {
+        $(
+            impl From<$S> for GsonV2 {
+                fn from(s: $S) -> Self {
+                    let strategy: TraversalStrategy = s.into();
+                    strategy.into()
+                }
+            }
+        )+
+    };
+}
+strategy_gson_impl![
+    PartitionStrategy,
+    SubgraphStrategy,
+    ReadOnlyStrategy,
+    OptionsStrategy,
+    SeedStrategy,
+    EdgeLabelVerificationStrategy,
+    ReservedKeysVerificationStrategy,
+    ElementIdStrategy,
+    HaltedTraverserStrategy
+];
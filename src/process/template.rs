@@ -0,0 +1,159 @@
+use super::*;
+use std::collections::{BTreeSet, HashMap};
+use thiserror::Error;
+
+// a traversal built once with Binding::var placeholders and executed with different values,
+// the values are sent as g:Binding so servers caching traversal plans can reuse the plan
+
+#[derive(Error, Debug, PartialEq)]
+pub enum BindError {
+    #[error("no value bound for {0}")]
+    Unbound(String),
+    #[error("{0} is not a placeholder of the template")]
+    UnknownKey(String),
+}
+
+#[derive(Clone, Debug)]
+pub struct Template<S = GsonV2, E = GsonV2> {
+    traversal: Traversal<S, E>,
+    keys: BTreeSet<String>,
+}
+
+impl<S, E> Template<S, E> {
+    pub fn new(mut traversal: Traversal<S, E>) -> Self {
+        let mut keys = BTreeSet::new();
+        for arg in traversal.bytecode_mut().args_mut() {
+            visit(arg, &mut |b| {
+                keys.insert(b.key.clone());
+            });
+        }
+        Self { traversal, keys }
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.keys.iter().map(String::as_str)
+    }
+
+    pub fn traversal(&self) -> &Traversal<S, E> {
+        &self.traversal
+    }
+
+    // a copy of the traversal with every placeholder set, all keys must be given a value
+    pub fn bind<K, V, I>(&self, values: I) -> Result<Traversal<S, E>, BindError>
+    where
+        K: Into<String>,
+        V: Into<GsonV2>,
+        I: IntoIterator<Item = (K, V)>,
+    {
+        let values: HashMap<String, GsonV2> = values
+            .into_iter()
+            .map(|(k, v)| (k.into(), v.into()))
+            .collect();
+        if let Some(key) = values.keys().find(|k| !self.keys.contains(*k)) {
+            return Err(BindError::UnknownKey(key.clone()));
+        }
+        if let Some(key) = self.keys.iter().find(|k| !values.contains_key(*k)) {
+            return Err(BindError::Unbound(key.clone()));
+        }
+
+        let mut traversal = self.traversal.clone();
+        for arg in traversal.bytecode_mut().args_mut() {
+            visit(arg, &mut |b| {
+                if let Some(v) = values.get(&b.key) {
+                    *b.value = v.clone();
+                }
+            });
+        }
+        Ok(traversal)
+    }
+}
+
+impl<S, E> From<Traversal<S, E>> for Template<S, E> {
+    fn from(traversal: Traversal<S, E>) -> Self {
+        Self::new(traversal)
+    }
+}
+
+// bindings may sit in nested traversals, lists, maps, predicates or strategy configuration
+fn visit<F: FnMut(&mut Binding)>(v: &mut GsonV2, f: &mut F) {
+    match v {
+        GsonV2::Binding(b) => f(b),
+        GsonV2::Bytecode(b) => b.args_mut().for_each(|a| visit(a, f)),
+        GsonV2::List(l) => l.iter_mut().for_each(|a| visit(a, f)),
+        GsonV2::Map(m) => m.values_mut().for_each(|a| visit(a, f)),
+        GsonV2::GMap(m) => m.0.iter_mut().for_each(|(k, v)| {
+            visit(k, f);
+            visit(v, f);
+        }),
+        GsonV2::Strategy(s) => s.configuration.values_mut().for_each(|a| visit(a, f)),
+        GsonV2::Predicate(p) => match p {
+            P::Eq(v) | P::Neq(v) | P::Lt(v) | P::Lte(v) | P::Gt(v) | P::Gte(v) | P::Not(v) => {
+                visit(&mut v.0, f)
+            }
+            P::Inside(r) | P::Outside(r) | P::Between(r) => {
+                visit(&mut r.0 .0 .0, f);
+                visit(&mut r.0 .1 .0, f);
+            }
+            P::Within(l) | P::Without(l) | P::And(l) | P::Or(l) => {
+                l.iter_mut().for_each(|a| visit(a, f))
+            }
+        },
+        _ => (),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+
+    fn steps<S, E>(t: &Traversal<S, E>) -> Value {
+        serde_json::to_value(t.bytecode()).unwrap()
+    }
+
+    #[test]
+    fn bind_placeholders() {
+        let template = Template::new(
+            g.V(Binding::var("id"))
+                .out("knows")
+                .has(("age", P::gt(Binding::var("age"))))
+                .where_(__.has(("name", P::within((Binding::var("name"), "josh"))))),
+        );
+        assert_eq!(template.keys().collect::<Vec<_>>(), ["age", "id", "name"]);
+
+        let t = template
+            .bind([("id", 1i64), ("age", 30i64), ("name", 2i64)])
+            .unwrap();
+        assert_eq!(
+            steps(&t)["step"][0],
+            json!(["V", {"@type": "g:Binding", "@value": {"key": "id", "value": {"@type": "g:Int64", "@value": 1}}}])
+        );
+        assert_eq!(
+            steps(&t)["step"][2][2]["@value"]["value"]["@value"]["value"],
+            json!({"@type": "g:Int64", "@value": 30})
+        );
+
+        // the template itself is unchanged and can be bound again
+        let t2 = template
+            .bind([("id", 2i64), ("age", 30i64), ("name", 2i64)])
+            .unwrap();
+        assert_ne!(steps(&t), steps(&t2));
+        assert_eq!(
+            steps(template.traversal())["step"][0][1]["@value"]["value"],
+            Value::Null
+        );
+    }
+
+    #[test]
+    fn bind_errors() {
+        let template: Template<_, _> = g.V(Binding::var("id")).into();
+        assert_eq!(
+            template.bind(Vec::<(&str, i64)>::new()).unwrap_err(),
+            BindError::Unbound("id".to_string())
+        );
+        assert_eq!(
+            template.bind([("id", 1i64), ("other", 2i64)]).unwrap_err(),
+            BindError::UnknownKey("other".to_string())
+        );
+    }
+}
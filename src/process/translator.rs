@@ -0,0 +1,453 @@
+use super::*;
+use crate::process::bytecode::Bytecode;
+
+use gremlin_rs_grammar::{builder_method, NO_ARG_STEPS};
+use std::{collections::HashMap, fmt};
+
+// renders bytecode as gremlin-groovy (pasteable into the gremlin console) or as the rust
+// builder calls producing it
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Translator {
+    Groovy,
+    Rust,
+}
+
+impl Translator {
+    pub fn translate(&self, alias: &str, bytecode: &Bytecode) -> String {
+        let source = match (self, alias) {
+            (Self::Groovy, a) => a.to_string(),
+            (Self::Rust, "g") => "g".to_string(),
+            (Self::Rust, a) => format!("TraversalSource::with_alias({:?})", a),
+        };
+        let s = self.steps(source, bytecode.sources());
+        self.steps(s, bytecode.steps())
+    }
+
+    fn steps(&self, mut s: String, steps: &[Vec<GsonV2>]) -> String {
+        for step in steps {
+            if let Some((GsonV2::String(op), args)) = step.split_first() {
+                s.push('.');
+                match self {
+                    // withoutStrategies takes strategy classes, not instances
+                    Self::Groovy if op == "withoutStrategies" => {
+                        let names: Vec<String> = args
+                            .iter()
+                            .map(|a| match a {
+                                GsonV2::Strategy(st) => st.name.clone(),
+                                a => groovy(a),
+                            })
+                            .collect();
+                        s.push_str(&format!("{}({})", op, names.join(", ")));
+                    }
+                    Self::Groovy => {
+                        s.push_str(op);
+                        s.push_str(&self.args(args));
+                    }
+                    Self::Rust => s.push_str(&rust_step(op, args)),
+                }
+            }
+        }
+        s
+    }
+
+    fn args(&self, args: &[GsonV2]) -> String {
+        let args: Vec<String> = args.iter().map(|a| self.value(a)).collect();
+        format!("({})", args.join(", "))
+    }
+
+    pub fn value(&self, v: &GsonV2) -> String {
+        match self {
+            Self::Groovy => groovy(v),
+            Self::Rust => rust(v),
+        }
+    }
+}
+
+impl<S, E> fmt::Display for Traversal<S, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&Translator::Groovy.translate(self.alias(), self.bytecode()))
+    }
+}
+
+// the name an enum value serializes to, e.g. "desc" for Order::Desc
+fn token_name<V: serde::Serialize>(v: &V) -> String {
+    match serde_json::to_value(v) {
+        Ok(serde_json::Value::String(s)) => s,
+        _ => String::new(),
+    }
+}
+
+// the name a predicate serializes under, which is also its builder method, e.g. "gt" for P::Gt
+fn predicate_name<V: serde::Serialize>(p: &V) -> String {
+    match serde_json::to_value(p) {
+        Ok(serde_json::Value::Object(m)) => match m.get("predicate") {
+            Some(serde_json::Value::String(s)) => s.clone(),
+            _ => String::new(),
+        },
+        _ => String::new(),
+    }
+}
+
+fn textp_parts(p: &TextP) -> (String, &str) {
+    let s = match p {
+        TextP::StartingWith(s)
+        | TextP::EndingWith(s)
+        | TextP::Containing(s)
+        | TextP::NotStartingWith(s)
+        | TextP::NotEndingWith(s)
+        | TextP::NotContaining(s)
+        | TextP::Regex(s)
+        | TextP::NotRegex(s) => s,
+    };
+    (predicate_name(p), s)
+}
+
+// nan and the infinities have no literal, both languages name them as constants of the type
+fn groovy_float(x: f64, class: &str, literal: String) -> String {
+    match x {
+        x if x.is_nan() => format!("{}.NaN", class),
+        f64::INFINITY => format!("{}.POSITIVE_INFINITY", class),
+        f64::NEG_INFINITY => format!("{}.NEGATIVE_INFINITY", class),
+        _ => literal,
+    }
+}
+
+fn rust_float(x: f64, ty: &str, literal: String) -> String {
+    match x {
+        x if x.is_nan() => format!("{}::NAN", ty),
+        f64::INFINITY => format!("{}::INFINITY", ty),
+        f64::NEG_INFINITY => format!("{}::NEG_INFINITY", ty),
+        _ => literal,
+    }
+}
+
+fn sorted<V>(map: &HashMap<String, V>) -> Vec<(&String, &V)> {
+    let mut entries: Vec<_> = map.iter().collect();
+    entries.sort_by(|a, b| a.0.cmp(b.0));
+    entries
+}
+
+fn groovy_str(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('\'');
+    for c in s.chars() {
+        match c {
+            '\'' => out.push_str("\\'"),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c => out.push(c),
+        }
+    }
+    out.push('\'');
+    out
+}
+
+fn groovy_list(values: &[GsonV2]) -> String {
+    let values: Vec<String> = values.iter().map(groovy).collect();
+    values.join(", ")
+}
+
+fn groovy(v: &GsonV2) -> String {
+    match v {
+        GsonV2::Date(ms) => format!("new Date({}L)", ms),
+        GsonV2::Timestamp(ms) => format!("new Timestamp({}L)", ms),
+        GsonV2::Double(d) => groovy_float(*d, "Double", format!("{:?}d", d)),
+        GsonV2::Float(f) => groovy_float(*f as f64, "Float", format!("{:?}f", f)),
+        GsonV2::Integer(i) => i.to_string(),
+        GsonV2::Long(l) => format!("{}L", l),
+        GsonV2::UUID(u) => format!("UUID.fromString('{}')", u),
+        GsonV2::Edge(e) => groovy(&e.id),
+        GsonV2::Vertex(v) => groovy(&v.id),
+        GsonV2::VertexProperty(p) => p.id.to_string(),
+        GsonV2::Property(p) => groovy(&p.value),
+        GsonV2::String(s) => groovy_str(s),
+        GsonV2::Bool(b) => b.to_string(),
+        GsonV2::Null => "null".to_string(),
+        GsonV2::Cardinality(c) => format!("VertexProperty.Cardinality.{}", token_name(c)),
+        GsonV2::Operator(o) => format!("Operator.{}", token_name(o)),
+        GsonV2::Predicate(p) => groovy_p(p),
+        GsonV2::TextPredicate(p) => format!("TextP.{}", groovy_textp(p)),
+        GsonV2::Order(o) => format!("Order.{}", token_name(o)),
+        GsonV2::Bytecode(b) => Translator::Groovy.steps("__".to_string(), b.steps()),
+        GsonV2::List(l) => format!("[{}]", groovy_list(l)),
+        GsonV2::Map(m) if m.is_empty() => "[:]".to_string(),
+        GsonV2::Map(m) => {
+            let entries: Vec<String> = sorted(m)
+                .into_iter()
+                .map(|(k, v)| format!("{}: {}", groovy_str(k), groovy(v)))
+                .collect();
+            format!("[{}]", entries.join(", "))
+        }
+        GsonV2::GMap(m) if m.is_empty() => "[:]".to_string(),
+        GsonV2::GMap(m) => {
+            let entries: Vec<String> = m
+                .iter()
+                .map(|(k, v)| match k {
+                    GsonV2::String(k) => format!("{}: {}", groovy_str(k), groovy(v)),
+                    k => format!("({}): {}", groovy(k), groovy(v)),
+                })
+                .collect();
+            format!("[{}]", entries.join(", "))
+        }
+        GsonV2::Path(p) => format!("[{}]", groovy_list(&p.objects)),
+        GsonV2::Binding(b) => b.key.clone(),
+        GsonV2::Pick(p) => format!("Pick.{}", token_name(p)),
+        GsonV2::Pop(p) => format!("Pop.{}", token_name(p)),
+        GsonV2::Scope(s) => format!("Scope.{}", token_name(s)),
+        GsonV2::T(t) => format!("T.{}", token_name(t)),
+        GsonV2::Direction(d) => format!("Direction.{}", token_name(d)),
+        GsonV2::Merge(m) => format!("Merge.{}", token_name(m)),
+        GsonV2::Column(c) => format!("Column.{}", token_name(c)),
+        GsonV2::Barrier(b) => format!("Barrier.{}", token_name(b)),
+        GsonV2::DT(d) => format!("DT.{}", token_name(d)),
+        GsonV2::Strategy(s) => {
+            let config: Vec<String> = sorted(&s.configuration)
+                .into_iter()
+                .map(|(k, v)| format!("{}: {}", k, groovy(v)))
+                .collect();
+            format!("new {}({})", s.name, config.join(", "))
+        }
+    }
+}
+
+fn groovy_p(p: &P) -> String {
+    let op = predicate_name(p);
+    let args = match p {
+        P::Eq(v) | P::Neq(v) | P::Lt(v) | P::Lte(v) | P::Gt(v) | P::Gte(v) | P::Not(v) => {
+            groovy(&v.0)
+        }
+        P::Inside(r) | P::Outside(r) | P::Between(r) => {
+            format!("{}, {}", groovy(&r.0 .0 .0), groovy(&r.0 .1 .0))
+        }
+        P::Within(l) | P::Without(l) => format!("[{}]", groovy_list(l)),
+        P::And(l) | P::Or(l) => {
+            let mut parts = l.iter().map(groovy);
+            let first = parts.next().unwrap_or_default();
+            return parts.fold(first, |acc, next| format!("{}.{}({})", acc, op, next));
+        }
+    };
+    format!("P.{}({})", op, args)
+}
+
+fn groovy_textp(p: &TextP) -> String {
+    let (op, s) = textp_parts(p);
+    format!("{}({})", op, groovy_str(s))
+}
+
+fn rust_tuple(args: &[GsonV2]) -> String {
+    let args: Vec<String> = args.iter().map(rust).collect();
+    match args.len() {
+        0 => "()".to_string(),
+        1 => args[0].clone(),
+        _ => format!("({})", args.join(", ")),
+    }
+}
+
+fn rust_step(op: &str, args: &[GsonV2]) -> String {
+    match (op, args) {
+        (op, []) if NO_ARG_STEPS.contains(&op) => format!("{}()", op),
+        ("toE", [direction, labels @ ..]) => {
+            format!("toE({}, {})", rust(direction), rust_tuple(labels))
+        }
+        (op, args) => format!("{}({})", builder_method(op), rust_tuple(args)),
+    }
+}
+
+fn rust_gson(v: &GsonV2) -> String {
+    match v {
+        GsonV2::Null | GsonV2::List(_) | GsonV2::Map(_) | GsonV2::GMap(_) | GsonV2::Path(_) => {
+            rust(v)
+        }
+        v => format!("GsonV2::from({})", rust(v)),
+    }
+}
+
+fn rust_map(map: &HashMap<String, GsonV2>) -> String {
+    let entries: Vec<String> = sorted(map)
+        .into_iter()
+        .map(|(k, v)| format!("({:?}.to_string(), {})", k, rust_gson(v)))
+        .collect();
+    format!("HashMap::from([{}])", entries.join(", "))
+}
+
+fn rust(v: &GsonV2) -> String {
+    match v {
+        GsonV2::Date(ms) => format!("GsonV2::Date({})", ms),
+        GsonV2::Timestamp(ms) => format!("GsonV2::Timestamp({})", ms),
+        GsonV2::Double(d) => rust_float(*d, "f64", format!("{:?}", d)),
+        GsonV2::Float(f) => rust_float(*f as f64, "f32", format!("{:?}f32", f)),
+        GsonV2::Integer(i) => i.to_string(),
+        GsonV2::Long(l) => format!("{}i64", l),
+        GsonV2::UUID(u) => format!("Uuid::parse_str({:?}).unwrap()", u.to_string()),
+        GsonV2::Edge(e) => rust(&e.id),
+        GsonV2::Vertex(v) => rust(&v.id),
+        GsonV2::VertexProperty(p) => p.id.to_string(),
+        GsonV2::Property(p) => rust(&p.value),
+        GsonV2::String(s) => format!("{:?}", s),
+        GsonV2::Bool(b) => b.to_string(),
+        GsonV2::Null => "GsonV2::Null".to_string(),
+        GsonV2::Cardinality(c) => format!("Cardinality::{:?}", c),
+        GsonV2::Operator(o) => format!("Operator::{:?}", o),
+        GsonV2::Predicate(p) => rust_p(p),
+        GsonV2::TextPredicate(p) => rust_textp(p),
+        GsonV2::Order(o) => format!("Order::{:?}", o),
+        GsonV2::Bytecode(b) => {
+            b.steps()
+                .iter()
+                .fold("__".to_string(), |s, step| match step.split_first() {
+                    Some((GsonV2::String(op), args)) => format!("{}.{}", s, rust_step(op, args)),
+                    _ => s,
+                })
+        }
+        GsonV2::List(l) => {
+            let values: Vec<String> = l.iter().map(rust_gson).collect();
+            format!("GsonV2::List(vec![{}])", values.join(", "))
+        }
+        GsonV2::Map(m) => format!("GsonV2::Map({})", rust_map(m)),
+        GsonV2::GMap(m) => {
+            let entries: Vec<String> = m
+                .iter()
+                .map(|(k, v)| format!(".with({}, {})", rust(k), rust(v)))
+                .collect();
+            format!("GsonV2::GMap(GsonMap::new(){})", entries.concat())
+        }
+        GsonV2::Path(p) => rust(&GsonV2::List(p.objects.clone())),
+        GsonV2::Binding(b) => format!("Binding::of({:?}, {})", b.key, rust(&b.value)),
+        GsonV2::Pick(p) => format!("Pick::{:?}", p),
+        GsonV2::Pop(p) => format!("Pop::{:?}", p),
+        GsonV2::Scope(s) => format!("Scope::{:?}", s),
+        GsonV2::T(t) => format!("T::{:?}", t),
+        GsonV2::Direction(d) => format!("Direction::{:?}", d),
+        GsonV2::Merge(m) => format!("Merge::{:?}", m),
+        GsonV2::Column(c) => format!("Column::{:?}", c),
+        GsonV2::Barrier(b) => format!("Barrier::{:?}", b),
+        GsonV2::DT(d) => format!("DT::{:?}", d),
+        GsonV2::Strategy(s) if s.configuration.is_empty() => {
+            format!("TraversalStrategy::new({:?})", s.name)
+        }
+        GsonV2::Strategy(s) => format!(
+            "TraversalStrategy {{ name: {:?}.to_string(), configuration: {} }}",
+            s.name,
+            rust_map(&s.configuration)
+        ),
+    }
+}
+
+fn rust_p(p: &P) -> String {
+    let op = predicate_name(p);
+    let args = match p {
+        P::Eq(v) | P::Neq(v) | P::Lt(v) | P::Lte(v) | P::Gt(v) | P::Gte(v) | P::Not(v) => {
+            rust(&v.0)
+        }
+        P::Inside(r) | P::Outside(r) | P::Between(r) => {
+            format!("{}, {}", rust(&r.0 .0 .0), rust(&r.0 .1 .0))
+        }
+        P::Within(l) | P::Without(l) => rust_tuple(l),
+        P::And(l) | P::Or(l) => {
+            let mut parts = l.iter().map(rust);
+            let first = parts.next().unwrap_or_default();
+            return parts.fold(first, |acc, next| format!("{}.{}({})", acc, op, next));
+        }
+    };
+    format!("P::{}({})", op, args)
+}
+
+fn rust_textp(p: &TextP) -> String {
+    let (op, s) = textp_parts(p);
+    format!("TextP::{}({:?})", op, s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::process::parser::parse_bytecode;
+
+    #[test]
+    fn groovy_translation() {
+        let t = g
+            .withStrategies(SeedStrategy { seed: 7 })
+            .withoutStrategies(TraversalStrategy::new("ReadOnlyStrategy"))
+            .V(())
+            .has(("person", "name", P::within(("marko", "josh"))))
+            .where_(__.out("created").count().is(P::gt(1).and(P::lt(5i64))))
+            .order()
+            .by(("age", Order::Desc))
+            .property((Cardinality::Single, "score", 1.5))
+            .values(("name", "it's"))
+            .limit((Scope::Local, 2i64));
+
+        let text = t.to_string();
+        assert_eq!(
+            text,
+            "g.withStrategies(new SeedStrategy(seed: 7L)).withoutStrategies(ReadOnlyStrategy).V()\
+            .has('person', 'name', P.within(['marko', 'josh']))\
+            .where(__.out('created').count().is(P.gt(1).and(P.lt(5L))))\
+            .order().by('age', Order.desc)\
+            .property(VertexProperty.Cardinality.single, 'score', 1.5d)\
+            .values('name', 'it\\'s').limit(Scope.local, 2L)"
+        );
+
+        // the parser reads its own output back
+        let parsed = parse_bytecode(&text).unwrap();
+        assert_eq!(
+            serde_json::to_value(parsed).unwrap(),
+            serde_json::to_value(t.bytecode()).unwrap()
+        );
+
+        let t = g
+            .mergeV(
+                GsonMap::new()
+                    .with(T::Label, "person")
+                    .with("name", "marko"),
+            )
+            .option((Merge::OnMatch, GsonMap::new()));
+        assert_eq!(
+            t.to_string(),
+            "g.mergeV([(T.label): 'person', 'name': 'marko']).option(Merge.onMatch, [:])"
+        );
+        assert_eq!(
+            Translator::Rust.translate(t.alias(), t.bytecode()),
+            "g.mergeV(GsonV2::GMap(GsonMap::new().with(T::Label, \"person\").with(\"name\", \"marko\")))\
+            .option((Merge::OnMatch, GsonV2::GMap(GsonMap::new())))"
+        );
+    }
+
+    #[test]
+    fn rust_translation() {
+        let t = TraversalSource::with_alias("gmodern")
+            .V("1")
+            .as_("a")
+            .out(())
+            .has(("name", TextP::containing("ar").negate()))
+            .toE(Direction::Out, "knows")
+            .constant(GsonV2::List(vec![1.into(), "x".into()]))
+            .select(("a", "b"))
+            .count();
+
+        assert_eq!(
+            Translator::Rust.translate(t.alias(), t.bytecode()),
+            "TraversalSource::with_alias(\"gmodern\").V(\"1\").as_(\"a\").out(())\
+            .has((\"name\", TextP::notContaining(\"ar\"))).toE(Direction::Out, \"knows\")\
+            .constant(GsonV2::List(vec![GsonV2::from(1), GsonV2::from(\"x\")]))\
+            .select((\"a\", \"b\")).count()"
+        );
+
+        let t = g
+            .V(Binding::of("id", 1i64))
+            .has(("score", P::gt(f64::NAN).or(P::lt(f32::NEG_INFINITY))))
+            .constant(f64::INFINITY);
+        assert_eq!(
+            t.to_string(),
+            "g.V(id).has('score', P.gt(Double.NaN).or(P.lt(Float.NEGATIVE_INFINITY)))\
+            .constant(Double.POSITIVE_INFINITY)"
+        );
+        assert_eq!(
+            Translator::Rust.translate(t.alias(), t.bytecode()),
+            "g.V(Binding::of(\"id\", 1i64)).has((\"score\", P::gt(f64::NAN).or(P::lt(f32::NEG_INFINITY))))\
+            .constant(f64::INFINITY)"
+        );
+    }
+}
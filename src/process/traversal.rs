@@ -1,155 +1,298 @@
-use crate::{driver::*, process::bytecode::*};
-
-#[derive(Debug)]
-pub struct Traversal {
+use crate::{
+    driver::*,
+    process::{
+        bytecode::*,
+        validator::{self, ValidationError},
+        *,
+    },
+    structure::{gson::GsonV2, Edge, Path, Vertex},
+};
+
+use serde::de::DeserializeOwned;
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    hash::Hash,
+    marker::PhantomData,
+    sync::Arc,
+};
+
+// S and E are the start and end types of the traversal, E is what to_list deserializes to
+pub struct Traversal<S = GsonV2, E = GsonV2> {
     bytecode: Bytecode,
+    alias: String,
+    remote: Option<Arc<dyn GremlinExecutor>>,
+    marker: PhantomData<fn() -> (S, E)>,
+}
+
+impl<S, E> Clone for Traversal<S, E> {
+    fn clone(&self) -> Self {
+        Traversal::from_source(self.alias.as_str(), self.bytecode.clone()).bind(self.remote.clone())
+    }
+}
+
+impl<S, E> fmt::Debug for Traversal<S, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Traversal")
+            .field("bytecode", &self.bytecode)
+            .field("alias", &self.alias)
+            .field("remote", &self.remote.is_some())
+            .finish()
+    }
 }
 
-impl Into<Bytecode> for Traversal {
+impl<S, E> Into<Bytecode> for Traversal<S, E> {
     fn into(self) -> Bytecode {
         self.bytecode
     }
 }
 
-impl Into<Bytecode> for &mut Traversal {
+impl<S, E> Into<Bytecode> for &mut Traversal<S, E> {
     fn into(self) -> Bytecode {
-        self.to_owned().bytecode
+        self.bytecode.clone()
     }
 }
 
-impl Clone for Traversal {
-    fn clone(&self) -> Self {
-        Traversal::new()
+impl Traversal {
+    pub fn new() -> Self {
+        Traversal::from_source("g", Bytecode::new())
     }
 }
 
 #[allow(non_snake_case)]
-impl Traversal {
-    pub fn new() -> Self {
+impl<S, E> Traversal<S, E> {
+    pub(crate) fn from_source<A: Into<String>>(alias: A, bytecode: Bytecode) -> Self {
         Traversal {
-            bytecode: Bytecode::new(),
+            bytecode,
+            alias: alias.into(),
+            remote: None,
+            marker: PhantomData,
+        }
+    }
+
+    pub(crate) fn bind(mut self, remote: Option<Arc<dyn GremlinExecutor>>) -> Self {
+        self.remote = remote;
+        self
+    }
+
+    pub(crate) fn retype<S2, E2>(self) -> Traversal<S2, E2> {
+        Traversal::from_source(self.alias, self.bytecode).bind(self.remote)
+    }
+
+    async fn submit<R: RemoteArg>(self, remote: R) -> Result<ClientResponse, ClientError> {
+        let bound = self.remote.clone();
+        match (remote.executor(), bound.as_deref()) {
+            (Some(x), _) | (None, Some(x)) => x.execute(self.retype()).await,
+            (None, None) => Err(ClientError::NoRemote),
         }
     }
 
+    pub fn alias(&self) -> &str {
+        &self.alias
+    }
+
+    pub fn bytecode(&self) -> &Bytecode {
+        &self.bytecode
+    }
+
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        validator::validate(&self.bytecode)
+    }
+
+    pub(crate) fn bytecode_mut(&mut self) -> &mut Bytecode {
+        &mut self.bytecode
+    }
+
+    // reinterprets the end type, e.g. after a step whose result shape is only known at runtime
+    pub fn cast<E2>(self) -> Traversal<S, E2> {
+        self.retype()
+    }
+
+    // the form taken by GremlinExecutor
+    pub fn untyped(self) -> Traversal {
+        self.retype()
+    }
+
+    pub fn append<S2, E2>(mut self, other: Traversal<S2, E2>) -> Traversal<S, E2> {
+        self.bytecode.extend(other.bytecode);
+        self.cast()
+    }
+
+    pub fn apply<T, F: FnOnce(Self) -> T>(self, fragment: F) -> T {
+        fragment(self)
+    }
+
+    pub fn step<A: Into<BytecodeStep>>(mut self, op: &str, args: A) -> Self {
+        self.bytecode.add_step(op, args);
+        self
+    }
+
     pub fn is_mutating(&self) -> bool {
         self.bytecode.is_mutating()
     }
 
-    pub async fn to_list(self, client: &Client) -> Result<ClientResponse, ClientError> {
-        client.execute(self).await
+    pub async fn to_list<R: RemoteArg>(self, remote: R) -> Result<Vec<E>, ClientError>
+    where
+        E: DeserializeOwned,
+    {
+        Ok(self.submit(remote).await?.parse()?)
+    }
+
+    pub async fn next<R: RemoteArg>(self, remote: R) -> Result<Option<E>, ClientError>
+    where
+        E: DeserializeOwned,
+    {
+        Ok(self.limit(1i64).to_list(remote).await?.into_iter().next())
     }
 
-    pub fn V<T: Into<BytecodeStep> + Clone>(mut self, args: T) -> Self {
+    pub async fn next_n<R: RemoteArg>(self, n: i64, remote: R) -> Result<Vec<E>, ClientError>
+    where
+        E: DeserializeOwned,
+    {
+        self.limit(n).to_list(remote).await
+    }
+
+    pub async fn has_next<R: RemoteArg>(self, remote: R) -> Result<bool, ClientError> {
+        let res = self.limit(1i64).submit(remote).await?;
+        Ok(!res.parse::<GsonV2>()?.is_empty())
+    }
+
+    pub async fn iterate<R: RemoteArg>(self, remote: R) -> Result<(), ClientError> {
+        self.none().submit(remote).await?;
+        Ok(())
+    }
+
+    pub async fn to_set<R: RemoteArg>(self, remote: R) -> Result<HashSet<E>, ClientError>
+    where
+        E: DeserializeOwned + Eq + Hash,
+    {
+        Ok(self.to_list(remote).await?.into_iter().collect())
+    }
+
+    // only fetches two results, enough to tell one from many
+    pub async fn one<R: RemoteArg>(self, remote: R) -> Result<E, ClientError>
+    where
+        E: DeserializeOwned,
+    {
+        let mut res = self.limit(2i64).to_list(remote).await?;
+        match res.len() {
+            0 => Err(ClientError::NoResult),
+            1 => Ok(res.remove(0)),
+            _ => Err(ClientError::MultipleResults),
+        }
+    }
+
+    pub fn V<T: Into<BytecodeStep> + Clone>(mut self, args: T) -> Traversal<S, Vertex> {
         self.bytecode.add_step("V", args);
-        self
+        self.cast()
     }
 
-    pub fn E<T: Into<BytecodeStep> + Clone>(mut self, args: T) -> Self {
+    pub fn E<T: Into<BytecodeStep> + Clone>(mut self, args: T) -> Traversal<S, Edge> {
         self.bytecode.add_step("E", args);
-        self
+        self.cast()
     }
 
-    pub fn addE<T: Into<BytecodeStep> + Clone>(mut self, args: T) -> Self {
+    pub fn addE<A: Label>(mut self, args: A) -> Traversal<S, Edge> {
         self.bytecode.add_step("addE", args);
-        self
+        self.cast()
     }
 
-    pub fn addV<T: Into<BytecodeStep> + Clone>(mut self, args: T) -> Self {
+    pub fn addV<A: OptionalLabel>(mut self, args: A) -> Traversal<S, Vertex> {
         self.bytecode.add_step("addV", args);
-        self
+        self.cast()
     }
 
-    pub fn aggregate<T: Into<BytecodeStep> + Clone>(mut self, args: T) -> Self {
+    pub fn aggregate<A: SideEffectKey>(mut self, args: A) -> Self {
         self.bytecode.add_step("aggregate", args);
         self
     }
 
-    pub fn and<T: Into<BytecodeStep> + Clone>(mut self, args: T) -> Self {
+    pub fn and<A: Traversals>(mut self, args: A) -> Self {
         self.bytecode.add_step("and", args);
         self
     }
 
-    pub fn as_<T: Into<BytecodeStep> + Clone>(mut self, args: T) -> Self {
+    pub fn as_<A: Labels>(mut self, args: A) -> Self {
         self.bytecode.add_step("as", args);
         self
     }
 
-    pub fn barrier<T: Into<BytecodeStep> + Clone>(mut self, args: T) -> Self {
+    pub fn barrier<A: BarrierArgs>(mut self, args: A) -> Self {
         self.bytecode.add_step("barrier", args);
         self
     }
 
-    pub fn both<T: Into<BytecodeStep> + Clone>(mut self, args: T) -> Self {
+    pub fn both<A: Keys>(mut self, args: A) -> Traversal<S, Vertex> {
         self.bytecode.add_step("both", args);
-        self
+        self.cast()
     }
 
-    pub fn bothE<T: Into<BytecodeStep> + Clone>(mut self, args: T) -> Self {
+    pub fn bothE<A: Keys>(mut self, args: A) -> Traversal<S, Edge> {
         self.bytecode.add_step("bothE", args);
-        self
+        self.cast()
     }
 
-    pub fn bothV<T: Into<BytecodeStep> + Clone>(mut self, args: T) -> Self {
-        self.bytecode.add_step("bothV", args);
-        self
+    pub fn bothV(mut self) -> Traversal<S, Vertex> {
+        self.bytecode.no_arg_step("bothV");
+        self.cast()
     }
 
-    pub fn branch(mut self) -> Self {
-        self.bytecode.no_arg_step("branch");
-        self
+    pub fn branch<S2, E2>(mut self, traversal: Traversal<S2, E2>) -> Traversal<S, GsonV2> {
+        self.bytecode.add_step("branch", traversal);
+        self.cast()
     }
 
-    pub fn by<T: Into<BytecodeStep> + Clone>(mut self, args: T) -> Self {
+    pub fn by<A: ByArgs>(mut self, args: A) -> Self {
         self.bytecode.add_step("by", args);
         self
     }
 
-    pub fn call<T: Into<BytecodeStep> + Clone>(mut self, args: T) -> Self {
+    pub fn call<A: CallArgs>(mut self, args: A) -> Traversal<S, GsonV2> {
         self.bytecode.add_step("call", args);
-        self
+        self.cast()
     }
 
-    pub fn cap<T: Into<BytecodeStep> + Clone>(mut self, args: T) -> Self {
+    pub fn cap<A: Labels>(mut self, args: A) -> Traversal<S, GsonV2> {
         self.bytecode.add_step("cap", args);
-        self
+        self.cast()
     }
 
-    pub fn choose<T: Into<BytecodeStep> + Clone>(mut self, args: T) -> Self {
+    pub fn choose<A: ChooseArgs>(mut self, args: A) -> Traversal<S, GsonV2> {
         self.bytecode.add_step("choose", args);
-        self
+        self.cast()
     }
 
-    pub fn coalesce<T: Into<BytecodeStep> + Clone>(mut self, args: T) -> Self {
+    pub fn coalesce<A: Traversals>(mut self, args: A) -> Traversal<S, GsonV2> {
         self.bytecode.add_step("coalesce", args);
-        self
+        self.cast()
     }
 
-    pub fn coin<T: Into<BytecodeStep> + Clone>(mut self, args: T) -> Self {
-        self.bytecode.add_step("coin", args);
+    pub fn coin(mut self, probability: f64) -> Self {
+        self.bytecode.add_step("coin", probability);
         self
     }
 
-    pub fn connectedComponent<T: Into<BytecodeStep> + Clone>(mut self, args: T) -> Self {
-        self.bytecode.add_step("connectedComponent", args);
+    pub fn connectedComponent(mut self) -> Self {
+        self.bytecode.no_arg_step("connectedComponent");
         self
     }
 
-    pub fn constant<T: Into<BytecodeStep> + Clone>(mut self, args: T) -> Self {
-        self.bytecode.add_step("constant", args);
-        self
+    pub fn constant<V: Into<GsonV2>>(mut self, value: V) -> Traversal<S, GsonV2> {
+        self.bytecode.add_step::<GsonV2>("constant", value.into());
+        self.cast()
     }
 
-    pub fn count(mut self) -> Self {
+    pub fn count(mut self) -> Traversal<S, i64> {
         self.bytecode.no_arg_step("count");
-        self
+        self.cast()
     }
 
-    pub fn cyclicPath<T: Into<BytecodeStep> + Clone>(mut self, args: T) -> Self {
-        self.bytecode.add_step("cyclicPath", args);
+    pub fn cyclicPath(mut self) -> Self {
+        self.bytecode.no_arg_step("cyclicPath");
         self
     }
 
-    pub fn dedup<T: Into<BytecodeStep> + Clone>(mut self, args: T) -> Self {
+    pub fn dedup<A: DedupArgs>(mut self, args: A) -> Self {
         self.bytecode.add_step("dedup", args);
         self
     }
@@ -159,17 +302,17 @@ impl Traversal {
         self
     }
 
-    pub fn element(mut self) -> Self {
+    pub fn element(mut self) -> Traversal<S, GsonV2> {
         self.bytecode.no_arg_step("element");
-        self
+        self.cast()
     }
 
-    pub fn elementMap<T: Into<BytecodeStep> + Clone>(mut self, args: T) -> Self {
+    pub fn elementMap<A: Keys>(mut self, args: A) -> Traversal<S, HashMap<String, GsonV2>> {
         self.bytecode.add_step("elementMap", args);
-        self
+        self.cast()
     }
 
-    pub fn emit<T: Into<BytecodeStep> + Clone>(mut self, args: T) -> Self {
+    pub fn emit<A: EmitArgs>(mut self, args: A) -> Self {
         self.bytecode.add_step("emit", args);
         self
     }
@@ -179,37 +322,37 @@ impl Traversal {
         self
     }
 
-    pub fn filter<T: Into<BytecodeStep> + Clone>(mut self, args: T) -> Self {
-        self.bytecode.add_step("filter", args);
+    pub fn filter<S2, E2>(mut self, traversal: Traversal<S2, E2>) -> Self {
+        self.bytecode.add_step("filter", traversal);
         self
     }
 
-    pub fn flatMap(mut self) -> Self {
-        self.bytecode.no_arg_step("flatMap");
-        self
+    pub fn flatMap<S2, E2>(mut self, traversal: Traversal<S2, E2>) -> Traversal<S, E2> {
+        self.bytecode.add_step("flatMap", traversal);
+        self.cast()
     }
 
-    pub fn fold(mut self) -> Self {
+    pub fn fold(mut self) -> Traversal<S, Vec<E>> {
         self.bytecode.no_arg_step("fold");
-        self
+        self.cast()
     }
 
-    pub fn from<T: Into<BytecodeStep> + Clone>(mut self, args: T) -> Self {
+    pub fn from<A: FromArgs>(mut self, args: A) -> Self {
         self.bytecode.add_step("from", args);
         self
     }
 
-    pub fn group<T: Into<BytecodeStep> + Clone>(mut self, args: T) -> Self {
+    pub fn group<A: OptionalKey>(mut self, args: A) -> Traversal<S, GsonV2> {
         self.bytecode.add_step("group", args);
-        self
+        self.cast()
     }
 
-    pub fn groupCount<T: Into<BytecodeStep> + Clone>(mut self, args: T) -> Self {
+    pub fn groupCount<A: OptionalKey>(mut self, args: A) -> Traversal<S, GsonV2> {
         self.bytecode.add_step("groupCount", args);
-        self
+        self.cast()
     }
 
-    pub fn has<T: Into<BytecodeStep> + Clone>(mut self, args: T) -> Self {
+    pub fn has<A: HasArgs>(mut self, args: A) -> Self {
         self.bytecode.add_step("has", args);
         self
     }
@@ -219,18 +362,18 @@ impl Traversal {
         self
     }
 
-    pub fn hasKey<T: Into<BytecodeStep> + Clone>(mut self, args: T) -> Self {
+    pub fn hasKey<A: KeysOrPredicate>(mut self, args: A) -> Self {
         self.bytecode.add_step("hasKey", args);
         self
     }
 
-    pub fn hasLabel<T: Into<BytecodeStep> + Clone>(mut self, args: T) -> Self {
+    pub fn hasLabel<A: KeysOrPredicate>(mut self, args: A) -> Self {
         self.bytecode.add_step("hasLabel", args);
         self
     }
 
-    pub fn hasNot<T: Into<BytecodeStep> + Clone>(mut self, args: T) -> Self {
-        self.bytecode.add_step("hasNot", args);
+    pub fn hasNot<K: StrArg>(mut self, key: K) -> Self {
+        self.bytecode.add_step("hasNot", key);
         self
     }
 
@@ -239,9 +382,9 @@ impl Traversal {
         self
     }
 
-    pub fn id(mut self) -> Self {
+    pub fn id(mut self) -> Traversal<S, GsonV2> {
         self.bytecode.no_arg_step("id");
-        self
+        self.cast()
     }
 
     pub fn identity(mut self) -> Self {
@@ -249,110 +392,110 @@ impl Traversal {
         self
     }
 
-    pub fn in_<T: Into<BytecodeStep> + Clone>(mut self, args: T) -> Self {
+    pub fn in_<A: Keys>(mut self, args: A) -> Traversal<S, Vertex> {
         self.bytecode.add_step("in", args);
-        self
+        self.cast()
     }
 
-    pub fn inE<T: Into<BytecodeStep> + Clone>(mut self, args: T) -> Self {
+    pub fn inE<A: Keys>(mut self, args: A) -> Traversal<S, Edge> {
         self.bytecode.add_step("inE", args);
-        self
+        self.cast()
     }
 
-    pub fn inV<T: Into<BytecodeStep> + Clone>(mut self, args: T) -> Self {
-        self.bytecode.add_step("inV", args);
-        self
+    pub fn inV(mut self) -> Traversal<S, Vertex> {
+        self.bytecode.no_arg_step("inV");
+        self.cast()
     }
 
-    pub fn index(mut self) -> Self {
+    pub fn index(mut self) -> Traversal<S, GsonV2> {
         self.bytecode.no_arg_step("index");
-        self
+        self.cast()
     }
 
-    pub fn inject<T: Into<BytecodeStep> + Clone>(mut self, args: T) -> Self {
+    pub fn inject<T: Into<BytecodeStep> + Clone>(mut self, args: T) -> Traversal<S, GsonV2> {
         self.bytecode.add_step("inject", args);
-        self
+        self.cast()
     }
 
-    pub fn is<T: Into<BytecodeStep> + Clone>(mut self, args: T) -> Self {
-        self.bytecode.add_step("is", args);
+    pub fn is<V: Into<GsonV2>>(mut self, value: V) -> Self {
+        self.bytecode.add_step::<GsonV2>("is", value.into());
         self
     }
 
-    pub fn key(mut self) -> Self {
+    pub fn key(mut self) -> Traversal<S, String> {
         self.bytecode.no_arg_step("key");
-        self
+        self.cast()
     }
 
-    pub fn label(mut self) -> Self {
+    pub fn label(mut self) -> Traversal<S, String> {
         self.bytecode.no_arg_step("label");
-        self
+        self.cast()
     }
 
-    pub fn limit<T: Into<BytecodeStep> + Clone>(mut self, args: T) -> Self {
+    pub fn limit<A: LimitArgs>(mut self, args: A) -> Self {
         self.bytecode.add_step("limit", args);
         self
     }
 
-    pub fn local<T: Into<BytecodeStep> + Clone>(mut self, args: T) -> Self {
-        self.bytecode.add_step("local", args);
-        self
+    pub fn local<S2, E2>(mut self, traversal: Traversal<S2, E2>) -> Traversal<S, E2> {
+        self.bytecode.add_step("local", traversal);
+        self.cast()
     }
 
-    pub fn loops(mut self) -> Self {
+    pub fn loops(mut self) -> Traversal<S, GsonV2> {
         self.bytecode.no_arg_step("loops");
-        self
+        self.cast()
     }
 
-    pub fn map<T: Into<BytecodeStep> + Clone>(mut self, args: T) -> Self {
-        self.bytecode.add_step("map", args);
-        self
+    pub fn map<S2, E2>(mut self, traversal: Traversal<S2, E2>) -> Traversal<S, E2> {
+        self.bytecode.add_step("map", traversal);
+        self.cast()
     }
 
-    pub fn match_<T: Into<BytecodeStep> + Clone>(mut self, args: T) -> Self {
+    pub fn match_<A: Traversals>(mut self, args: A) -> Self {
         self.bytecode.add_step("match", args);
         self
     }
 
-    pub fn math<T: Into<BytecodeStep> + Clone>(mut self, args: T) -> Self {
-        self.bytecode.add_step("math", args);
-        self
+    pub fn math<K: StrArg>(mut self, expression: K) -> Traversal<S, f64> {
+        self.bytecode.add_step("math", expression);
+        self.cast()
     }
 
-    pub fn max(mut self) -> Self {
+    pub fn max(mut self) -> Traversal<S, GsonV2> {
         self.bytecode.no_arg_step("max");
-        self
+        self.cast()
     }
-    pub fn mean(mut self) -> Self {
+    pub fn mean(mut self) -> Traversal<S, f64> {
         self.bytecode.no_arg_step("mean");
-        self
+        self.cast()
     }
-    pub fn mergeE<T: Into<BytecodeStep> + Clone>(mut self, args: T) -> Self {
+    pub fn mergeE<A: MergeArgs>(mut self, args: A) -> Traversal<S, Edge> {
         self.bytecode.add_step("mergeE", args);
-        self
+        self.cast()
     }
-    pub fn mergeV<T: Into<BytecodeStep> + Clone>(mut self, args: T) -> Self {
+    pub fn mergeV<A: MergeArgs>(mut self, args: A) -> Traversal<S, Vertex> {
         self.bytecode.add_step("mergeV", args);
-        self
+        self.cast()
     }
-    pub fn min(mut self) -> Self {
+    pub fn min(mut self) -> Traversal<S, GsonV2> {
         self.bytecode.no_arg_step("min");
-        self
+        self.cast()
     }
     pub fn none(mut self) -> Self {
         self.bytecode.no_arg_step("none");
         self
     }
-    pub fn not<T: Into<BytecodeStep> + Clone>(mut self, args: T) -> Self {
-        self.bytecode.add_step("not", args);
+    pub fn not<S2, E2>(mut self, traversal: Traversal<S2, E2>) -> Self {
+        self.bytecode.add_step("not", traversal);
         self
     }
 
-    pub fn option<T: Into<BytecodeStep> + Clone>(mut self, args: T) -> Self {
+    pub fn option<A: OptionArgs>(mut self, args: A) -> Self {
         self.bytecode.add_step("option", args);
         self
     }
-    pub fn or<T: Into<BytecodeStep> + Clone>(mut self, args: T) -> Self {
+    pub fn or<A: Traversals>(mut self, args: A) -> Self {
         self.bytecode.add_step("or", args);
         self
     }
@@ -361,170 +504,172 @@ impl Traversal {
         self
     }
 
-    pub fn otherV<T: Into<BytecodeStep> + Clone>(mut self, args: T) -> Self {
-        self.bytecode.add_step("otherV", args);
-        self
+    pub fn otherV(mut self) -> Traversal<S, Vertex> {
+        self.bytecode.no_arg_step("otherV");
+        self.cast()
     }
-    pub fn out<T: Into<BytecodeStep> + Clone>(mut self, args: T) -> Self {
+    pub fn out<A: Keys>(mut self, args: A) -> Traversal<S, Vertex> {
         self.bytecode.add_step("out", args);
-        self
+        self.cast()
     }
-    pub fn outE<T: Into<BytecodeStep> + Clone>(mut self, args: T) -> Self {
+    pub fn outE<A: Keys>(mut self, args: A) -> Traversal<S, Edge> {
         self.bytecode.add_step("outE", args);
-        self
+        self.cast()
     }
 
-    pub fn outV<T: Into<BytecodeStep> + Clone>(mut self, args: T) -> Self {
-        self.bytecode.add_step("outV", args);
-        self
+    pub fn outV(mut self) -> Traversal<S, Vertex> {
+        self.bytecode.no_arg_step("outV");
+        self.cast()
     }
     pub fn pageRank(mut self) -> Self {
         self.bytecode.no_arg_step("pageRank");
         self
     }
-    pub fn path(mut self) -> Self {
+    pub fn path(mut self) -> Traversal<S, Path> {
         self.bytecode.no_arg_step("path");
-        self
+        self.cast()
     }
     pub fn peerPressure(mut self) -> Self {
         self.bytecode.no_arg_step("peerPressure");
         self
     }
-    pub fn profile(mut self) -> Self {
+    pub fn profile(mut self) -> Traversal<S, GsonV2> {
         self.bytecode.no_arg_step("profile");
-        self
+        self.cast()
     }
     pub fn program<T: Into<BytecodeStep> + Clone>(mut self, args: T) -> Self {
         self.bytecode.add_step("program", args);
         self
     }
-    pub fn project<T: Into<BytecodeStep> + Clone>(mut self, args: T) -> Self {
+    pub fn project<A: Labels>(mut self, args: A) -> Traversal<S, HashMap<String, GsonV2>> {
         self.bytecode.add_step("project", args);
-        self
+        self.cast()
     }
-    pub fn properties<T: Into<BytecodeStep> + Clone>(mut self, args: T) -> Self {
+    pub fn properties<A: Keys>(mut self, args: A) -> Traversal<S, GsonV2> {
         self.bytecode.add_step("properties", args);
-        self
+        self.cast()
     }
-    pub fn property<T: Into<BytecodeStep> + Clone>(mut self, args: T) -> Self {
+    pub fn property<A: PropertyArgs>(mut self, args: A) -> Self {
         self.bytecode.add_step("property", args);
         self
     }
-    pub fn propertyMap<T: Into<BytecodeStep> + Clone>(mut self, args: T) -> Self {
+    pub fn propertyMap<A: Keys>(mut self, args: A) -> Traversal<S, HashMap<String, GsonV2>> {
         self.bytecode.add_step("propertyMap", args);
-        self
+        self.cast()
     }
-    pub fn range<T: Into<BytecodeStep> + Clone>(mut self, args: T) -> Self {
+    pub fn range<A: RangeArgs>(mut self, args: A) -> Self {
         self.bytecode.add_step("range", args);
         self
     }
-    pub fn read<T: Into<BytecodeStep> + Clone>(mut self, args: T) -> Self {
-        self.bytecode.add_step("read", args);
+    pub fn read(mut self) -> Self {
+        self.bytecode.no_arg_step("read");
         self
     }
-    pub fn repeat<T: Into<BytecodeStep> + Clone>(mut self, args: T) -> Self {
+    pub fn repeat<A: RepeatArgs>(mut self, args: A) -> Self {
         self.bytecode.add_step("repeat", args);
         self
     }
 
-    pub fn sack<T: Into<BytecodeStep> + Clone>(mut self, args: T) -> Self {
+    pub fn sack<A: SackArgs>(mut self, args: A) -> Self {
         self.bytecode.add_step("sack", args);
         self
     }
-    pub fn sample<T: Into<BytecodeStep> + Clone>(mut self, args: T) -> Self {
+    pub fn sample<A: SampleArgs>(mut self, args: A) -> Self {
         self.bytecode.add_step("sample", args);
         self
     }
-    pub fn select<T: Into<BytecodeStep> + Clone>(mut self, args: T) -> Self {
+    pub fn select<A: SelectArgs>(mut self, args: A) -> Traversal<S, GsonV2> {
         self.bytecode.add_step("select", args);
-        self
+        self.cast()
     }
-    pub fn shortestPath<T: Into<BytecodeStep> + Clone>(mut self, args: T) -> Self {
-        self.bytecode.add_step("shortestPath", args);
-        self
+    pub fn shortestPath(mut self) -> Traversal<S, Path> {
+        self.bytecode.no_arg_step("shortestPath");
+        self.cast()
     }
-    pub fn sideEffect<T: Into<BytecodeStep> + Clone>(mut self, args: T) -> Self {
-        self.bytecode.add_step("sideEffect", args);
+    pub fn sideEffect<S2, E2>(mut self, traversal: Traversal<S2, E2>) -> Self {
+        self.bytecode.add_step("sideEffect", traversal);
         self
     }
-    pub fn simplePath<T: Into<BytecodeStep> + Clone>(mut self, args: T) -> Self {
-        self.bytecode.add_step("simplePath", args);
+    pub fn simplePath(mut self) -> Self {
+        self.bytecode.no_arg_step("simplePath");
         self
     }
-    pub fn skip<T: Into<BytecodeStep> + Clone>(mut self, args: T) -> Self {
+    pub fn skip<A: LimitArgs>(mut self, args: A) -> Self {
         self.bytecode.add_step("skip", args);
         self
     }
-    pub fn store<T: Into<BytecodeStep> + Clone>(mut self, args: T) -> Self {
-        self.bytecode.add_step("store", args);
+    pub fn store<K: StrArg>(mut self, key: K) -> Self {
+        self.bytecode.add_step("store", key);
         self
     }
-    pub fn subgraph<T: Into<BytecodeStep> + Clone>(mut self, args: T) -> Self {
-        self.bytecode.add_step("subgraph", args);
+    pub fn subgraph<K: StrArg>(mut self, key: K) -> Self {
+        self.bytecode.add_step("subgraph", key);
         self
     }
-    pub fn sum(mut self) -> Self {
+    pub fn sum(mut self) -> Traversal<S, GsonV2> {
         self.bytecode.no_arg_step("sum");
-        self
+        self.cast()
     }
-    pub fn tail<T: Into<BytecodeStep> + Clone>(mut self, args: T) -> Self {
+    pub fn tail<A: TailArgs>(mut self, args: A) -> Self {
         self.bytecode.add_step("tail", args);
         self
     }
-    pub fn timeLimit<T: Into<BytecodeStep> + Clone>(mut self, args: T) -> Self {
-        self.bytecode.add_step("timeLimit", args);
+    pub fn timeLimit(mut self, millis: i64) -> Self {
+        self.bytecode.add_step("timeLimit", millis);
         self
     }
-    pub fn times<T: Into<BytecodeStep> + Clone>(mut self, args: T) -> Self {
-        self.bytecode.add_step("times", args);
+    pub fn times(mut self, max_loops: i32) -> Self {
+        self.bytecode.add_step("times", max_loops);
         self
     }
-    pub fn to<T: Into<BytecodeStep> + Clone>(mut self, args: T) -> Self {
+    pub fn to<A: FromArgs>(mut self, args: A) -> Self {
         self.bytecode.add_step("to", args);
         self
     }
-    pub fn toE<T: Into<BytecodeStep> + Clone>(mut self, args: T) -> Self {
-        self.bytecode.add_step("toE", args);
-        self
+    pub fn toE<L: Keys>(mut self, direction: Direction, labels: L) -> Traversal<S, Edge> {
+        let mut step: BytecodeStep = labels.into();
+        step.0.insert(0, direction.into());
+        self.bytecode.add_step("toE", step);
+        self.cast()
     }
-    pub fn toV<T: Into<BytecodeStep> + Clone>(mut self, args: T) -> Self {
-        self.bytecode.add_step("toV", args);
-        self
+    pub fn toV(mut self, direction: Direction) -> Traversal<S, Vertex> {
+        self.bytecode.add_step("toV", direction);
+        self.cast()
     }
-    pub fn tree<T: Into<BytecodeStep> + Clone>(mut self, args: T) -> Self {
+    pub fn tree<A: OptionalKey>(mut self, args: A) -> Traversal<S, GsonV2> {
         self.bytecode.add_step("tree", args);
-        self
+        self.cast()
     }
-    pub fn unfold(mut self) -> Self {
+    pub fn unfold(mut self) -> Traversal<S, GsonV2> {
         self.bytecode.no_arg_step("unfold");
-        self
+        self.cast()
     }
-    pub fn union<T: Into<BytecodeStep> + Clone>(mut self, args: T) -> Self {
+    pub fn union<A: Traversals>(mut self, args: A) -> Traversal<S, GsonV2> {
         self.bytecode.add_step("union", args);
-        self
+        self.cast()
     }
-    pub fn until<T: Into<BytecodeStep> + Clone>(mut self, args: T) -> Self {
+    pub fn until<A: Condition>(mut self, args: A) -> Self {
         self.bytecode.add_step("until", args);
         self
     }
-    pub fn value(mut self) -> Self {
+    pub fn value(mut self) -> Traversal<S, GsonV2> {
         self.bytecode.no_arg_step("value");
-        self
+        self.cast()
     }
-    pub fn valueMap<T: Into<BytecodeStep> + Clone>(mut self, args: T) -> Self {
+    pub fn valueMap<A: ValueMapArgs>(mut self, args: A) -> Traversal<S, HashMap<String, GsonV2>> {
         self.bytecode.add_step("valueMap", args);
-        self
+        self.cast()
     }
 
-    pub fn values<T: Into<BytecodeStep> + Clone>(mut self, args: T) -> Self {
+    pub fn values<A: Keys>(mut self, args: A) -> Traversal<S, GsonV2> {
         self.bytecode.add_step("values", args);
-        self
+        self.cast()
     }
-    pub fn where_<T: Into<BytecodeStep> + Clone>(mut self, args: T) -> Self {
+    pub fn where_<A: WhereArgs>(mut self, args: A) -> Self {
         self.bytecode.add_step("where", args);
         self
     }
-    pub fn with<T: Into<BytecodeStep> + Clone>(mut self, args: T) -> Self {
+    pub fn with<A: WithArgs>(mut self, args: A) -> Self {
         self.bytecode.add_step("with", args);
         self
     }
@@ -0,0 +1,441 @@
+use super::*;
+use crate::structure::{Edge, Vertex};
+
+// the newest upsert form the server runs, TinkerPop 3.6 added mergeV and mergeE, older servers
+// get the fold().coalesce(unfold(), add) idiom
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ServerCapability {
+    #[default]
+    TinkerPop34,
+    TinkerPop36,
+}
+
+type Properties = Vec<(String, GsonV2)>;
+
+// keys find the element and are written on create, on_create properties are only written to a
+// new element and on_match properties only to an existing one
+#[derive(Clone, Debug)]
+pub struct VertexUpsert {
+    source: TraversalSource,
+    label: String,
+    id: Option<GsonV2>,
+    keys: Properties,
+    on_create: Properties,
+    on_match: Properties,
+}
+
+impl VertexUpsert {
+    pub(crate) fn new<L: Into<String>>(source: &TraversalSource, label: L) -> Self {
+        Self {
+            source: source.clone(),
+            label: label.into(),
+            id: None,
+            keys: vec![],
+            on_create: vec![],
+            on_match: vec![],
+        }
+    }
+
+    // finds the vertex by id (and label) alone, a created vertex is given the id. keys are then
+    // written on match as well as on create, as a vertex whose keys changed would be missed
+    // by the search and adding its id again fails
+    pub fn id<I: Into<GsonV2>>(mut self, id: I) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    pub fn key<K: Into<String>, V: Into<GsonV2>>(mut self, key: K, value: V) -> Self {
+        self.keys.push((key.into(), value.into()));
+        self
+    }
+
+    pub fn on_create<K: Into<String>, V: Into<GsonV2>>(mut self, key: K, value: V) -> Self {
+        self.on_create.push((key.into(), value.into()));
+        self
+    }
+
+    pub fn on_match<K: Into<String>, V: Into<GsonV2>>(mut self, key: K, value: V) -> Self {
+        self.on_match.push((key.into(), value.into()));
+        self
+    }
+
+    pub fn traversal(mut self) -> Traversal<Vertex, Vertex> {
+        if self.id.is_some() {
+            let keys = std::mem::take(&mut self.keys);
+            self.on_create = keys.iter().cloned().chain(self.on_create).collect();
+            self.on_match = keys.into_iter().chain(self.on_match).collect();
+        }
+        match self.source.capability() {
+            ServerCapability::TinkerPop36 => {
+                let tokens = GsonMap::new().with(T::Label, self.label);
+                let tokens = match self.id {
+                    Some(id) => tokens.with(T::Id, id),
+                    None => tokens,
+                };
+                let search = merge_map(tokens, self.keys);
+                merge_options(
+                    self.source.mergeV(search.clone()),
+                    search,
+                    self.on_create,
+                    self.on_match,
+                )
+            }
+            ServerCapability::TinkerPop34 => {
+                let start = match self.id.clone() {
+                    Some(id) => self.source.V(id),
+                    None => self.source.V(()),
+                };
+                let found = self
+                    .keys
+                    .iter()
+                    .cloned()
+                    .fold(start.hasLabel(self.label.as_str()), |t, kv| t.has(kv));
+                let matched = self.on_match.into_iter().fold(__.unfold(), |t, (k, v)| {
+                    t.property((Cardinality::Single, k, v))
+                });
+                let add = match self.id {
+                    Some(id) => __.addV(self.label).property((T::Id, id)),
+                    None => __.addV(self.label),
+                };
+                let created = self
+                    .keys
+                    .into_iter()
+                    .chain(self.on_create)
+                    .fold(add, |t, kv| t.property(kv));
+                found.fold().coalesce((matched, created)).cast()
+            }
+        }
+    }
+}
+
+impl From<VertexUpsert> for Traversal<Vertex, Vertex> {
+    fn from(upsert: VertexUpsert) -> Self {
+        upsert.traversal()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct EdgeUpsert {
+    source: TraversalSource,
+    label: String,
+    out_v: GsonV2,
+    in_v: GsonV2,
+    keys: Properties,
+    on_create: Properties,
+    on_match: Properties,
+}
+
+impl EdgeUpsert {
+    pub(crate) fn new<L, O, I>(source: &TraversalSource, label: L, out_v: O, in_v: I) -> Self
+    where
+        L: Into<String>,
+        O: Into<GsonV2>,
+        I: Into<GsonV2>,
+    {
+        Self {
+            source: source.clone(),
+            label: label.into(),
+            out_v: out_v.into(),
+            in_v: in_v.into(),
+            keys: vec![],
+            on_create: vec![],
+            on_match: vec![],
+        }
+    }
+
+    pub fn key<K: Into<String>, V: Into<GsonV2>>(mut self, key: K, value: V) -> Self {
+        self.keys.push((key.into(), value.into()));
+        self
+    }
+
+    pub fn on_create<K: Into<String>, V: Into<GsonV2>>(mut self, key: K, value: V) -> Self {
+        self.on_create.push((key.into(), value.into()));
+        self
+    }
+
+    pub fn on_match<K: Into<String>, V: Into<GsonV2>>(mut self, key: K, value: V) -> Self {
+        self.on_match.push((key.into(), value.into()));
+        self
+    }
+
+    pub fn traversal(self) -> Traversal<Vertex, Edge> {
+        match self.source.capability() {
+            ServerCapability::TinkerPop36 => {
+                let search = GsonMap::new()
+                    .with(T::Label, self.label)
+                    .with(Direction::Out, self.out_v)
+                    .with(Direction::In, self.in_v);
+                let search = merge_map(search, self.keys);
+                // retyped to the start of the coalesce form, which starts from the out vertex
+                merge_options(
+                    self.source.mergeE(search.clone()),
+                    search,
+                    self.on_create,
+                    self.on_match,
+                )
+                .retype()
+            }
+            ServerCapability::TinkerPop34 => {
+                let found = self.keys.iter().cloned().fold(
+                    self.source
+                        .V(self.out_v.clone())
+                        .outE(self.label.as_str())
+                        .where_(__.inV().hasId(self.in_v.clone())),
+                    |t, kv| t.has(kv),
+                );
+                let matched = self
+                    .on_match
+                    .into_iter()
+                    .fold(__.unfold(), |t, kv| t.property(kv));
+                let created = self.keys.into_iter().chain(self.on_create).fold(
+                    __.addE(self.label)
+                        .from(__.V(self.out_v))
+                        .to(__.V(self.in_v)),
+                    |t, kv| t.property(kv),
+                );
+                found.fold().coalesce((matched, created)).cast()
+            }
+        }
+    }
+}
+
+impl From<EdgeUpsert> for Traversal<Vertex, Edge> {
+    fn from(upsert: EdgeUpsert) -> Self {
+        upsert.traversal()
+    }
+}
+
+// 3.6 servers create from the onCreate map alone when it is given, so it repeats the search
+// entries (3.7 accepts them as they match the search map)
+fn merge_options<S, E>(
+    mut t: Traversal<S, E>,
+    search: GsonMap,
+    on_create: Properties,
+    on_match: Properties,
+) -> Traversal<S, E> {
+    if !on_create.is_empty() {
+        t = t.option((Merge::OnCreate, merge_map(search, on_create)));
+    }
+    if !on_match.is_empty() {
+        t = t.option((Merge::OnMatch, merge_map(GsonMap::new(), on_match)));
+    }
+    t
+}
+
+fn merge_map(tokens: GsonMap, properties: Properties) -> GsonMap {
+    properties
+        .into_iter()
+        .fold(tokens, |map, (k, v)| map.with(k, v))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+
+    fn steps<S, E>(t: Traversal<S, E>) -> Value {
+        serde_json::to_value(t.bytecode()).unwrap()
+    }
+
+    fn map(entries: &[(GsonV2, GsonV2)]) -> GsonV2 {
+        GsonV2::GMap(GsonMap(entries.to_vec()))
+    }
+
+    #[test]
+    fn vertex_upserts() {
+        let upsert = |source: &TraversalSource| {
+            source
+                .upsert_v("person")
+                .key("name", "marko")
+                .on_create("age", 29)
+                .on_match("seen", 1i64)
+                .traversal()
+        };
+        assert_eq!(g.capability(), ServerCapability::TinkerPop34);
+        assert_eq!(
+            steps(upsert(&g)),
+            steps(
+                g.V(())
+                    .hasLabel("person")
+                    .has(("name", "marko"))
+                    .fold()
+                    .coalesce((
+                        __.unfold().property((Cardinality::Single, "seen", 1i64)),
+                        __.addV("person")
+                            .property(("name", "marko"))
+                            .property(("age", 29))
+                    ))
+            )
+        );
+
+        let g36 = g.with_capability(ServerCapability::TinkerPop36);
+        assert_eq!(
+            steps(upsert(&g36)),
+            steps(
+                g.mergeV(map(&[
+                    (T::Label.into(), "person".into()),
+                    ("name".into(), "marko".into())
+                ]))
+                .option((
+                    Merge::OnCreate,
+                    map(&[
+                        (T::Label.into(), "person".into()),
+                        ("name".into(), "marko".into()),
+                        ("age".into(), 29.into())
+                    ])
+                ))
+                .option((Merge::OnMatch, map(&[("seen".into(), 1i64.into())])))
+            )
+        );
+        assert_eq!(
+            steps(g36.upsert_v("person").key("name", "marko").traversal()),
+            steps(g.mergeV(map(&[
+                (T::Label.into(), "person".into()),
+                ("name".into(), "marko".into())
+            ])))
+        );
+    }
+
+    #[test]
+    fn vertex_upsert_by_id() {
+        let upsert = |source: &TraversalSource| {
+            source
+                .upsert_v("person")
+                .id(1i64)
+                .on_create("name", "marko")
+                .traversal()
+        };
+        assert_eq!(
+            steps(upsert(&g)),
+            steps(
+                g.V(1i64).hasLabel("person").fold().coalesce((
+                    __.unfold(),
+                    __.addV("person")
+                        .property((T::Id, 1i64))
+                        .property(("name", "marko"))
+                ))
+            )
+        );
+        assert_eq!(
+            steps(upsert(&g.with_capability(ServerCapability::TinkerPop36))),
+            steps(
+                g.mergeV(map(&[
+                    (T::Label.into(), "person".into()),
+                    (T::Id.into(), 1i64.into())
+                ]))
+                .option((
+                    Merge::OnCreate,
+                    map(&[
+                        (T::Label.into(), "person".into()),
+                        (T::Id.into(), 1i64.into()),
+                        ("name".into(), "marko".into())
+                    ])
+                ))
+            )
+        );
+    }
+
+    #[test]
+    fn vertex_upsert_by_id_and_key() {
+        let upsert = |source: &TraversalSource| {
+            source
+                .upsert_v("person")
+                .id(1i64)
+                .key("name", "marko")
+                .traversal()
+        };
+        assert_eq!(
+            steps(upsert(&g)),
+            steps(
+                g.V(1i64).hasLabel("person").fold().coalesce((
+                    __.unfold().property((Cardinality::Single, "name", "marko")),
+                    __.addV("person")
+                        .property((T::Id, 1i64))
+                        .property(("name", "marko"))
+                ))
+            )
+        );
+        let search = map(&[
+            (T::Label.into(), "person".into()),
+            (T::Id.into(), 1i64.into()),
+        ]);
+        assert_eq!(
+            steps(upsert(&g.with_capability(ServerCapability::TinkerPop36))),
+            steps(
+                g.mergeV(search)
+                    .option((
+                        Merge::OnCreate,
+                        map(&[
+                            (T::Label.into(), "person".into()),
+                            (T::Id.into(), 1i64.into()),
+                            ("name".into(), "marko".into())
+                        ])
+                    ))
+                    .option((Merge::OnMatch, map(&[("name".into(), "marko".into())])))
+            )
+        );
+    }
+
+    // 3.6 servers create from this map alone, so it has to name the label and the keys
+    #[test]
+    fn create_map_repeats_search() {
+        let t = g
+            .with_capability(ServerCapability::TinkerPop36)
+            .upsert_v("person")
+            .key("name", "marko")
+            .on_create("age", 29)
+            .traversal();
+        let steps = steps(t);
+        let search = steps["step"][0][1]["@value"].as_array().unwrap();
+        let create = steps["step"][1][2]["@value"].as_array().unwrap();
+        assert_eq!(steps["step"][1][1]["@value"], "onCreate");
+        assert_eq!(create[..search.len()], search[..]);
+        assert_eq!(create[search.len()], "age");
+        assert_eq!(create.len(), search.len() + 2);
+    }
+
+    #[test]
+    fn edge_upserts() {
+        let upsert = |source: &TraversalSource| -> Traversal<Vertex, Edge> {
+            source
+                .upsert_e("knows", 1i64, 2i64)
+                .on_create("weight", 0.5)
+                .into()
+        };
+        assert_eq!(
+            steps(upsert(&g)),
+            steps(
+                g.V(1i64)
+                    .outE("knows")
+                    .where_(__.inV().hasId(2i64))
+                    .fold()
+                    .coalesce((
+                        __.unfold(),
+                        __.addE("knows")
+                            .from(__.V(1i64))
+                            .to(__.V(2i64))
+                            .property(("weight", 0.5))
+                    ))
+            )
+        );
+        assert_eq!(
+            steps(upsert(&g.with_capability(ServerCapability::TinkerPop36))),
+            steps(
+                g.mergeE(map(&[
+                    (T::Label.into(), "knows".into()),
+                    (Direction::Out.into(), 1i64.into()),
+                    (Direction::In.into(), 2i64.into())
+                ]))
+                .option((
+                    Merge::OnCreate,
+                    map(&[
+                        (T::Label.into(), "knows".into()),
+                        (Direction::Out.into(), 1i64.into()),
+                        (Direction::In.into(), 2i64.into()),
+                        ("weight".into(), 0.5.into())
+                    ])
+                ))
+            )
+        );
+    }
+}
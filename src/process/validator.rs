@@ -0,0 +1,216 @@
+use super::*;
+use crate::process::bytecode::Bytecode;
+
+use thiserror::Error;
+
+// client side checks of step ordering and modulator rules, so traversals the server would reject
+// fail before a round trip. positions are step indexes, a nested traversal's steps are appended
+// to the position of the step taking it (e.g. 2.1 is the 2nd step of step 2's argument)
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ValidationError {
+    #[error("by() at step {} cannot modulate {1}", position(.0))]
+    UnexpectedBy(Vec<usize>, String),
+    #[error("times() at step {} has no repeat() to modulate", position(.0))]
+    TimesWithoutRepeat(Vec<usize>),
+    #[error("option() at step {} cannot modulate {1}, expected choose, branch, mergeV or mergeE", position(.0))]
+    UnexpectedOption(Vec<usize>, String),
+    #[error("{1}() at step {} cannot modulate {2}, expected addE or a path step", position(.0))]
+    UnexpectedFromTo(Vec<usize>, String, String),
+}
+
+fn position(path: &[usize]) -> String {
+    let path: Vec<String> = path.iter().map(usize::to_string).collect();
+    path.join(".")
+}
+
+// modulators, and as() which only labels the step before it
+const MODULATORS: [&str; 9] = [
+    "as", "by", "emit", "from", "option", "times", "to", "until", "with",
+];
+
+const BY_STEPS: [&str; 19] = [
+    "aggregate",
+    "cyclicPath",
+    "dedup",
+    "group",
+    "groupCount",
+    "math",
+    "order",
+    "pageRank",
+    "path",
+    "peerPressure",
+    "project",
+    "sack",
+    "sample",
+    "select",
+    "simplePath",
+    "store",
+    "tree",
+    "valueMap",
+    "where",
+];
+
+const OPTION_STEPS: [&str; 4] = ["branch", "choose", "mergeE", "mergeV"];
+
+// path filters take from/to as well as addE
+const FROM_TO_STEPS: [&str; 4] = ["addE", "cyclicPath", "path", "simplePath"];
+
+pub fn validate(bytecode: &Bytecode) -> Result<(), ValidationError> {
+    validate_steps(bytecode, &mut Vec::new())
+}
+
+fn validate_steps(bytecode: &Bytecode, path: &mut Vec<usize>) -> Result<(), ValidationError> {
+    let ops: Vec<&str> = bytecode
+        .steps()
+        .iter()
+        .map(|step| match step.first() {
+            Some(GsonV2::String(op)) => op.as_str(),
+            _ => "",
+        })
+        .collect();
+
+    for (i, step) in bytecode.steps().iter().enumerate() {
+        path.push(i);
+        // the step a modulator applies to, and for times() the repeat() it may precede
+        let modulated = ops[..i].iter().rev().find(|op| !MODULATORS.contains(op));
+        let next = ops[i + 1..].iter().find(|op| !MODULATORS.contains(op));
+        // from/to may follow the property() steps set on the element addE/addV creates
+        let added = ops[..i]
+            .iter()
+            .rev()
+            .find(|op| !MODULATORS.contains(op) && **op != "property");
+        let endpoints = match added {
+            Some(&("addE" | "addV")) => added,
+            _ => modulated,
+        };
+        let name = |modulated: Option<&&str>| match modulated {
+            Some(op) => format!("{}()", op),
+            None => "the traversal start".to_string(),
+        };
+
+        match ops[i] {
+            "by" if !modulated.is_some_and(|op| BY_STEPS.contains(op)) => {
+                return Err(ValidationError::UnexpectedBy(path.clone(), name(modulated)))
+            }
+            "times" if modulated != Some(&"repeat") && next != Some(&"repeat") => {
+                return Err(ValidationError::TimesWithoutRepeat(path.clone()))
+            }
+            "option" if !modulated.is_some_and(|op| OPTION_STEPS.contains(op)) => {
+                return Err(ValidationError::UnexpectedOption(
+                    path.clone(),
+                    name(modulated),
+                ))
+            }
+            op @ ("from" | "to") if !endpoints.is_some_and(|op| FROM_TO_STEPS.contains(op)) => {
+                return Err(ValidationError::UnexpectedFromTo(
+                    path.clone(),
+                    op.to_string(),
+                    name(endpoints),
+                ))
+            }
+            _ => (),
+        }
+
+        for arg in step.iter().skip(1) {
+            if let GsonV2::Bytecode(nested) = arg {
+                validate_steps(nested, path)?;
+            }
+        }
+        path.pop();
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn valid_traversals() {
+        let traversals = [
+            g.V(())
+                .order()
+                .by(("age", Order::Desc))
+                .by("name")
+                .untyped(),
+            g.V(()).repeat(__.out(())).times(2).untyped(),
+            g.V(()).times(2).repeat(__.out(())).emit(()).untyped(),
+            g.V(()).repeat(__.out(())).emit(()).times(2).untyped(),
+            g.V(())
+                .as_("a")
+                .out(())
+                .as_("b")
+                .select(("a", "b"))
+                .by("name")
+                .by(T::Id)
+                .untyped(),
+            g.V(1i64)
+                .addE("knows")
+                .as_("e")
+                .to(__.V(2i64))
+                .from("a")
+                .untyped(),
+            g.V(())
+                .choose(__.values("age"))
+                .option((27, __.out(())))
+                .untyped(),
+            g.V(())
+                .mergeV(())
+                .option((Merge::OnCreate, GsonV2::Null))
+                .untyped(),
+            g.V(()).out(()).simplePath().from("a").to("b").untyped(),
+            g.addE("knows")
+                .property(("weight", 1))
+                .from(__.V(1i64))
+                .to(__.V(2i64))
+                .untyped(),
+            g.V(()).pageRank().by(__.outE("knows")).untyped(),
+            g.V(()).peerPressure().by("cluster").untyped(),
+        ];
+        for t in traversals {
+            assert_eq!(t.validate(), Ok(()), "{}", t);
+        }
+    }
+
+    #[test]
+    fn invalid_traversals() {
+        assert_eq!(
+            g.V(()).out(()).by("name").validate(),
+            Err(ValidationError::UnexpectedBy(vec![2], "out()".to_string()))
+        );
+        assert_eq!(
+            g.V(()).out(()).times(2).validate(),
+            Err(ValidationError::TimesWithoutRepeat(vec![2]))
+        );
+        assert_eq!(
+            g.V(()).union(__.out(()).option((1, __.in_(())))).validate(),
+            Err(ValidationError::UnexpectedOption(
+                vec![1, 1],
+                "out()".to_string()
+            ))
+        );
+        assert_eq!(
+            g.V(()).out(()).from("a").validate(),
+            Err(ValidationError::UnexpectedFromTo(
+                vec![2],
+                "from".to_string(),
+                "out()".to_string()
+            ))
+        );
+        assert_eq!(
+            g.V(()).property(("a", 1)).to("b").validate(),
+            Err(ValidationError::UnexpectedFromTo(
+                vec![2],
+                "to".to_string(),
+                "property()".to_string()
+            ))
+        );
+        assert_eq!(
+            validate(g.V(()).local(__.by("name")).bytecode())
+                .unwrap_err()
+                .to_string(),
+            "by() at step 1.0 cannot modulate the traversal start"
+        );
+    }
+}
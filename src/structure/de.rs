@@ -1,6 +1,7 @@
 use std::num::*;
 use std::str::{from_utf8, FromStr, Utf8Error};
 
+use super::gson::GsonV2;
 use serde::de::{
     self, Deserialize, DeserializeSeed, EnumAccess, IntoDeserializer, MapAccess, SeqAccess,
     VariantAccess,
@@ -32,6 +33,12 @@ pub fn from_vec<'de, T: Deserialize<'de>>(v: &'de Vec<u8>) -> GResult<T> {
     T::deserialize(&mut d)
 }
 
+// deserializes an already parsed value, e.g. one property of a valueMap result
+pub fn from_gson<T: de::DeserializeOwned>(v: &GsonV2) -> GResult<T> {
+    let json = serde_json::to_vec(v).map_err(|e| CustomError(e.to_string()))?;
+    from_vec(&json)
+}
+
 #[derive(Error, Debug)]
 pub enum GsonError {
     #[error(transparent)]
@@ -380,25 +387,12 @@ impl<'de, 'a> de::Deserializer<'de> for &'a mut Deserializer<'de> {
                             b"g:Int64" => self.deserialize_i64(visitor),
                             b"g:Timestamp" => self.deserialize_i64(visitor),
                             b"g:UUID" => self.deserialize_str(visitor),
-                            b"g:Edge" => self.deserialize_struct(
-                                "Edge",
-                                &["id", "label", "inVLabel", "outVLabel", "inV", "outV"],
-                                visitor,
-                            ),
-                            b"g:Path" => {
-                                self.deserialize_struct("Path", &["labels", "objects"], visitor)
-                            }
-                            b"g:Property" => {
-                                self.deserialize_struct("Property", &["key", "value"], visitor)
+                            // the visitor receives the typed wrapper, see deserialize_struct
+                            b"g:Edge" | b"g:Path" | b"g:Property" | b"g:Vertex"
+                            | b"g:VertexProperty" | b"g:Map" | b"g:T" | b"g:Direction"
+                            | b"g:Operator" | b"g:Column" | b"g:Barrier" | b"g:DT" => {
+                                self.deserialize_map(visitor)
                             }
-                            b"g:Vertex" => {
-                                self.deserialize_struct("Vertex", &["id", "label"], visitor)
-                            }
-                            b"g:VertexProperty" => self.deserialize_struct(
-                                "VertexProperty",
-                                &["id", "value", "label"],
-                                visitor,
-                            ),
                             _ => return Err(GsonError::invalid_str("g:Identifier", t)),
                         }
                     }
@@ -667,6 +661,16 @@ impl<'de, 'a> de::Deserializer<'de> for &'a mut Deserializer<'de> {
     {
         #[cfg(test)]
         println!("deserializing struct: {}", _name);
+
+        // typed graph elements (g:Vertex, g:Edge...) deserialize from their @value
+        if self.input.len() > 9 && *br#"{"@type":"# == self.input[..9] {
+            self.get_gv_type()?;
+            let val = self.deserialize_map(visitor)?;
+            return match self.next_byte()? {
+                b'}' => Ok(val),
+                x => Err(GsonError::invalid_char("}", x)),
+            };
+        }
         let val = self.deserialize_map(visitor);
         val
     }
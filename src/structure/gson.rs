@@ -1,7 +1,12 @@
 use super::*;
 use crate::process::*;
 use serde::{de::*, ser::*};
-use std::{collections::HashMap, fmt::Debug, marker::PhantomData, time};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Debug,
+    marker::PhantomData,
+    time,
+};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "@type", content = "@value")]
@@ -104,6 +109,84 @@ impl From<()> for GsonLiteral {
     }
 }
 
+// a map keyed by any value, e.g. the T.label and Direction.OUT / IN keys of mergeV and mergeE.
+// one keyed only by strings serializes as a plain object like GsonV2::Map, which v2 servers
+// read, any other key makes it a g:Map of alternating keys and values. g:Map is a GraphSON 3
+// type while the client still sends application/vnd.gremlin-v2.0+json (responses are read as
+// GraphSON 2), so enum keys need a server that reads requests of that mimetype with a GraphSON 3
+// serializer, e.g. GraphSONMessageSerializerV3 registered for it
+#[derive(Debug, Clone, Default)]
+pub struct GsonMap(pub Vec<(GsonV2, GsonV2)>);
+
+impl GsonMap {
+    pub fn new() -> Self {
+        Self(vec![])
+    }
+
+    // keys of mixed types, e.g. GsonMap::new().with(T::Label, "person").with("name", "marko")
+    pub fn with<K: Into<GsonV2>, V: Into<GsonV2>>(mut self, key: K, value: V) -> Self {
+        self.0.push((key.into(), value.into()));
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&GsonV2, &GsonV2)> {
+        self.0.iter().map(|(k, v)| (k, v))
+    }
+
+    fn has_string_keys(&self) -> bool {
+        self.0.iter().all(|(k, _)| matches!(k, GsonV2::String(_)))
+    }
+
+    fn flattened(&self) -> Vec<&GsonV2> {
+        self.0.iter().flat_map(|(k, v)| [k, v]).collect()
+    }
+
+    fn from_flattened<E: serde::de::Error>(values: Vec<GsonV2>) -> Result<Self, E> {
+        if !values.len().is_multiple_of(2) {
+            return Err(E::invalid_length(
+                values.len(),
+                &"alternating keys and values",
+            ));
+        }
+        let mut values = values.into_iter();
+        let mut map = Self::new();
+        while let (Some(k), Some(v)) = (values.next(), values.next()) {
+            map.0.push((k, v));
+        }
+        Ok(map)
+    }
+}
+
+impl<K: Into<GsonV2>, V: Into<GsonV2>> FromIterator<(K, V)> for GsonMap {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        Self(
+            iter.into_iter()
+                .map(|(k, v)| (k.into(), v.into()))
+                .collect(),
+        )
+    }
+}
+
+impl<K: Into<GsonV2>, V: Into<GsonV2>> From<HashMap<K, V>> for GsonMap {
+    fn from(map: HashMap<K, V>) -> Self {
+        map.into_iter().collect()
+    }
+}
+
+impl<K: Into<GsonV2>, V: Into<GsonV2>> From<BTreeMap<K, V>> for GsonMap {
+    fn from(map: BTreeMap<K, V>) -> Self {
+        map.into_iter().collect()
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum GsonV2 {
     Date(i64),
@@ -128,6 +211,7 @@ pub enum GsonV2 {
     Bytecode(bytecode::Bytecode),
     List(Vec<GsonV2>),
     Map(HashMap<String, GsonV2>),
+    GMap(GsonMap),
     Path(Path),
     Binding(Binding),
     Pick(Pick),
@@ -136,6 +220,10 @@ pub enum GsonV2 {
     T(T),
     Direction(Direction),
     Merge(Merge),
+    Column(Column),
+    Barrier(Barrier),
+    DT(DT),
+    Strategy(TraversalStrategy),
 }
 
 impl Serialize for GsonV2 {
@@ -161,6 +249,13 @@ impl Serialize for GsonV2 {
                 }
                 map.end()
             }
+            Self::GMap(m) if m.has_string_keys() => {
+                let mut map = serializer.serialize_map(Some(m.len()))?;
+                for (k, v) in m.iter() {
+                    map.serialize_entry(k, v)?;
+                }
+                map.end()
+            }
 
             _ => {
                 let mut map = serializer.serialize_map(Some(2))?;
@@ -261,10 +356,30 @@ impl Serialize for GsonV2 {
                         map.serialize_entry("@type", "g:Merge")?;
                         map.serialize_entry("@value", d)?;
                     }
+                    Self::GMap(d) => {
+                        map.serialize_entry("@type", "g:Map")?;
+                        map.serialize_entry("@value", &d.flattened())?;
+                    }
+                    Self::Column(d) => {
+                        map.serialize_entry("@type", "g:Column")?;
+                        map.serialize_entry("@value", d)?;
+                    }
+                    Self::Barrier(d) => {
+                        map.serialize_entry("@type", "g:Barrier")?;
+                        map.serialize_entry("@value", d)?;
+                    }
+                    Self::DT(d) => {
+                        map.serialize_entry("@type", "g:DT")?;
+                        map.serialize_entry("@value", d)?;
+                    }
                     Self::Binding(d) => {
                         map.serialize_entry("@type", "g:Binding")?;
                         map.serialize_entry("@value", d)?;
                     }
+                    Self::Strategy(d) => {
+                        map.serialize_entry("@type", &format!("g:{}", d.name))?;
+                        map.serialize_entry("@value", &d.configuration)?;
+                    }
                     Self::String(_) => panic!(),
                     Self::Bool(_) => panic!(),
                     Self::Null => panic!(),
@@ -484,6 +599,34 @@ impl<'de> Visitor<'de> for GsonVisitor {
                         }
                         return Err(serde::de::Error::missing_field("@value"));
                     }
+                    "g:Map" => match map.next_entry::<&str, Vec<GsonV2>>()? {
+                        Some(("@value", v)) => {
+                            map.next_entry::<(), ()>()?;
+                            GsonMap::from_flattened(v).map(GsonV2::GMap)
+                        }
+                        _ => Err(serde::de::Error::missing_field("@value")),
+                    },
+                    "g:Column" => match map.next_entry::<&str, Column>()? {
+                        Some(("@value", v)) => {
+                            map.next_entry::<(), ()>()?;
+                            Ok(GsonV2::Column(v))
+                        }
+                        _ => Err(serde::de::Error::missing_field("@value")),
+                    },
+                    "g:Barrier" => match map.next_entry::<&str, Barrier>()? {
+                        Some(("@value", v)) => {
+                            map.next_entry::<(), ()>()?;
+                            Ok(GsonV2::Barrier(v))
+                        }
+                        _ => Err(serde::de::Error::missing_field("@value")),
+                    },
+                    "g:DT" => match map.next_entry::<&str, DT>()? {
+                        Some(("@value", v)) => {
+                            map.next_entry::<(), ()>()?;
+                            Ok(GsonV2::DT(v))
+                        }
+                        _ => Err(serde::de::Error::missing_field("@value")),
+                    },
                     "g:Merge" => {
                         if let Some(("@value", v)) = map.next_entry::<&str, Merge>()? {
                             map.next_entry::<(), ()>()?;
@@ -526,6 +669,20 @@ impl<'de> Visitor<'de> for GsonVisitor {
                         }
                         return Err(serde::de::Error::missing_field("@value"));
                     }
+                    x if x.ends_with("Strategy") => {
+                        let name = x.strip_prefix("g:").ok_or_else(|| {
+                            serde::de::Error::invalid_value(Unexpected::Str(x), &"g:<Strategy>")
+                        })?;
+                        match map.next_entry::<&str, HashMap<String, GsonV2>>()? {
+                            Some(("@value", v)) => {
+                                map.next_entry::<(), ()>()?;
+                                let mut strategy = TraversalStrategy::new(name);
+                                strategy.configuration = v;
+                                Ok(GsonV2::Strategy(strategy))
+                            }
+                            _ => Err(serde::de::Error::missing_field("@value")),
+                        }
+                    }
                     x => {
                         return Err(serde::de::Error::invalid_value(
                             Unexpected::Str(x),
@@ -687,6 +844,78 @@ impl From<Order> for GsonV2 {
     }
 }
 
+impl From<Pick> for GsonV2 {
+    fn from(c: Pick) -> Self {
+        Self::Pick(c)
+    }
+}
+
+impl From<Pop> for GsonV2 {
+    fn from(c: Pop) -> Self {
+        Self::Pop(c)
+    }
+}
+
+impl From<Scope> for GsonV2 {
+    fn from(c: Scope) -> Self {
+        Self::Scope(c)
+    }
+}
+
+impl From<T> for GsonV2 {
+    fn from(c: T) -> Self {
+        Self::T(c)
+    }
+}
+
+impl From<Direction> for GsonV2 {
+    fn from(c: Direction) -> Self {
+        Self::Direction(c)
+    }
+}
+
+impl From<Merge> for GsonV2 {
+    fn from(c: Merge) -> Self {
+        Self::Merge(c)
+    }
+}
+
+impl From<GsonMap> for GsonV2 {
+    fn from(m: GsonMap) -> Self {
+        Self::GMap(m)
+    }
+}
+
+impl<K: Into<GsonV2>, V: Into<GsonV2>> From<HashMap<K, V>> for GsonV2 {
+    fn from(m: HashMap<K, V>) -> Self {
+        Self::GMap(m.into())
+    }
+}
+
+impl<K: Into<GsonV2>, V: Into<GsonV2>> From<BTreeMap<K, V>> for GsonV2 {
+    fn from(m: BTreeMap<K, V>) -> Self {
+        Self::GMap(m.into())
+    }
+}
+
+impl From<Column> for GsonV2 {
+    fn from(c: Column) -> Self {
+        Self::Column(c)
+    }
+}
+
+impl From<Barrier> for GsonV2 {
+    fn from(c: Barrier) -> Self {
+        Self::Barrier(c)
+    }
+}
+
+impl From<DT> for GsonV2 {
+    fn from(c: DT) -> Self {
+        Self::DT(c)
+    }
+}
+
 impl From<P> for GsonV2 {
     fn from(p: P) -> Self {
         Self::Predicate(p)
@@ -699,14 +928,26 @@ impl From<TextP> for GsonV2 {
     }
 }
 
+impl From<Binding> for GsonV2 {
+    fn from(b: Binding) -> Self {
+        Self::Binding(b)
+    }
+}
+
 impl From<bytecode::Bytecode> for GsonV2 {
     fn from(b: bytecode::Bytecode) -> Self {
         Self::Bytecode(b)
     }
 }
 
-impl From<Traversal> for GsonV2 {
-    fn from(t: Traversal) -> Self {
+impl From<TraversalStrategy> for GsonV2 {
+    fn from(s: TraversalStrategy) -> Self {
+        Self::Strategy(s)
+    }
+}
+
+impl<S, E> From<Traversal<S, E>> for GsonV2 {
+    fn from(t: Traversal<S, E>) -> Self {
         let b: bytecode::Bytecode = t.into();
         b.into()
     }